@@ -0,0 +1,144 @@
+//! Show additional columns next to the tree like the
+//! detail view of a file manager.
+
+use egui::{Label, ThemePreference};
+use egui_ltreeview::{
+    node::NodeBuilder, Action, Column, SortColumn, SortDirection, TreeView, TreeViewBuilder,
+};
+
+fn main() -> Result<(), eframe::Error> {
+    //env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([500.0, 500.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "Egui_ltreeview columns example",
+        options,
+        Box::new(|cc| {
+            cc.egui_ctx
+                .options_mut(|options| options.theme_preference = ThemePreference::Dark);
+            Ok(Box::<MyApp>::default())
+        }),
+    )
+}
+
+struct File {
+    id: i32,
+    name: &'static str,
+    size: u32,
+    kind: &'static str,
+}
+
+struct MyApp {
+    documents: Vec<File>,
+    pictures: Vec<File>,
+    sort: Option<(SortColumn, SortDirection)>,
+}
+
+impl Default for MyApp {
+    fn default() -> Self {
+        Self {
+            documents: vec![
+                File::new(2, "notes.txt", 1, "Text"),
+                File::new(3, "report.pdf", 230, "PDF"),
+                File::new(4, "todo.md", 2, "Markdown"),
+            ],
+            pictures: vec![
+                File::new(6, "holiday.png", 2400, "Image"),
+                File::new(7, "cat.jpg", 800, "Image"),
+            ],
+            sort: None,
+        }
+    }
+}
+
+impl File {
+    fn new(id: i32, name: &'static str, size: u32, kind: &'static str) -> Self {
+        Self {
+            id,
+            name,
+            size,
+            kind,
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let response = TreeView::new(ui.make_persistent_id("Names tree view"))
+                .tree_column_header("Name")
+                .tree_column_sortable(true)
+                .column(Column::new("Size").width(70.0).sortable(true))
+                .column(Column::new("Type").width(90.0).sortable(true))
+                .show(ui, |mut builder| {
+                    dir(&mut builder, 0, "Root");
+                    dir(&mut builder, 1, "Documents");
+                    for file in self.documents.iter() {
+                        show_file(&mut builder, file);
+                    }
+                    builder.close_dir();
+                    dir(&mut builder, 5, "Pictures");
+                    for file in self.pictures.iter() {
+                        show_file(&mut builder, file);
+                    }
+                    builder.close_dir();
+                    builder.close_dir();
+                });
+            for action in response.actions {
+                if let Action::SortBy { column, direction } = action {
+                    self.sort = direction.map(|direction| (column, direction));
+                }
+            }
+        });
+
+        if let Some((column, direction)) = self.sort {
+            for files in [&mut self.documents, &mut self.pictures] {
+                files.sort_by(|a, b| {
+                    let ordering = match column {
+                        SortColumn::Tree => a.name.cmp(b.name),
+                        SortColumn::Column(0) => a.size.cmp(&b.size),
+                        SortColumn::Column(_) => a.kind.cmp(b.kind),
+                    };
+                    match direction {
+                        SortDirection::Ascending => ordering,
+                        SortDirection::Descending => ordering.reverse(),
+                    }
+                });
+            }
+        } else {
+            for files in [&mut self.documents, &mut self.pictures] {
+                files.sort_by_key(|file| file.id);
+            }
+        }
+    }
+}
+
+fn dir(builder: &mut TreeViewBuilder<i32>, id: i32, name: &str) {
+    builder.node(
+        NodeBuilder::dir(id)
+            .label(|ui| {
+                ui.add(Label::new(name).selectable(false));
+            })
+            .column(|_| {})
+            .column(|ui| {
+                ui.add(Label::new("Folder").selectable(false));
+            }),
+    );
+}
+
+fn show_file(builder: &mut TreeViewBuilder<i32>, file: &File) {
+    builder.node(
+        NodeBuilder::leaf(file.id)
+            .label(|ui| {
+                ui.add(Label::new(file.name).selectable(false));
+            })
+            .column(|ui| {
+                ui.add(Label::new(format!("{} KB", file.size)).selectable(false));
+            })
+            .column(|ui| {
+                ui.add(Label::new(file.kind).selectable(false));
+            }),
+    );
+}
@@ -22,6 +22,8 @@ pub struct File {
     pub id: Uuid,
     pub name: String,
     pub icon: bool,
+    pub enabled: bool,
+    pub modified: bool,
 }
 
 impl Node {
@@ -40,6 +42,8 @@ impl Node {
             id: Uuid::new_v4(),
             name: String::from(name),
             icon: true,
+            enabled: true,
+            modified: false,
         })
     }
 
@@ -50,6 +54,22 @@ impl Node {
         }
     }
 
+    pub fn name(&self) -> &str {
+        match self {
+            Node::Directory(dir) => &dir.name,
+            Node::File(file) => &file.name,
+        }
+    }
+
+    pub fn for_each(&self, action: &mut dyn FnMut(&Node)) {
+        (action)(self);
+        if let Node::Directory(dir) = self {
+            for node in dir.children.iter() {
+                node.for_each(action);
+            }
+        }
+    }
+
     pub fn find_mut(&mut self, id: &Uuid, action: &mut dyn FnMut(&mut Node)) {
         if self.id() == id {
             (action)(self);
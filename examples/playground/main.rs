@@ -1,9 +1,12 @@
 mod data;
-use std::env;
+use std::{collections::HashSet, env};
 
 use data::*;
 use egui::{Color32, DragValue, Id, Label, Layout, Response, Ui};
-use egui_ltreeview::{node::NodeBuilder, Action, RowLayout, TreeView, TreeViewBuilder, VLineStyle};
+use egui_ltreeview::{
+    node::NodeBuilder, Action, Decoration, RowDensity, RowLayout, TreeBreadcrumbs, TreeView,
+    TreeViewBuilder, VLineStyle,
+};
 use uuid::Uuid;
 
 fn main() -> Result<(), eframe::Error> {
@@ -25,6 +28,7 @@ fn main() -> Result<(), eframe::Error> {
 
 struct MyApp {
     tree: Node,
+    search: String,
     settings_id: Uuid,
     selected_node: Option<Uuid>,
     settings: Settings,
@@ -48,12 +52,21 @@ struct Settings {
     min_height_enabled: bool,
     min_height: f32,
     show_size: bool,
+    row_hover_highlight: bool,
+    striped: bool,
+    animate_dirs: bool,
+    sticky_headers: bool,
+    highlight_active_vline: bool,
+    default_icons: bool,
+    density: RowDensity,
+    right_to_left: bool,
 }
 
 impl Default for MyApp {
     fn default() -> Self {
         Self {
             tree: make_tree(),
+            search: String::new(),
             settings_id: Uuid::new_v4(),
             selected_node: None,
             settings: Settings {
@@ -85,6 +98,12 @@ impl eframe::App for MyApp {
                 );
             });
         egui::CentralPanel::default().show(ctx, |ui| {
+            let breadcrumbs =
+                TreeBreadcrumbs::new(Id::new("tree view").with("Names tree view")).show::<Uuid>(ui);
+            if let Some(selected) = breadcrumbs.inner {
+                self.selected_node = Some(selected);
+            }
+            ui.separator();
             if let Some(selected_node) = self.selected_node.as_ref() {
                 if selected_node == &self.settings_id {
                     show_settings(ui, &mut self.settings);
@@ -99,7 +118,20 @@ impl eframe::App for MyApp {
 }
 
 fn show_tree_view(ui: &mut Ui, app: &mut MyApp) -> Response {
-    let response = TreeView::new(ui.make_persistent_id("Names tree view"))
+    ui.add(egui::TextEdit::singleline(&mut app.search).hint_text("Search"));
+    let search = app.search.to_lowercase();
+    let mut matching_nodes = HashSet::new();
+    app.tree.for_each(&mut |node| {
+        if node.name().to_lowercase().contains(&search) {
+            matching_nodes.insert(*node.id());
+        }
+    });
+
+    let mut tree_view = TreeView::new(Id::new("tree view").with("Names tree view"));
+    if !search.is_empty() {
+        tree_view = tree_view.filter(|id| matching_nodes.contains(id));
+    }
+    let response = tree_view
         .override_indent(app.settings.override_indent)
         .vline_style(app.settings.vline_style)
         .row_layout(app.settings.row_layout)
@@ -125,6 +157,25 @@ fn show_tree_view(ui: &mut Ui, app: &mut MyApp) -> Response {
         } else {
             0.0
         })
+        .row_hover_highlight(app.settings.row_hover_highlight)
+        .striped(app.settings.striped)
+        .animate_dirs(app.settings.animate_dirs)
+        .sticky_headers(app.settings.sticky_headers)
+        .highlight_active_vline(app.settings.highlight_active_vline)
+        .default_icons(app.settings.default_icons)
+        .density(app.settings.density)
+        .right_to_left(app.settings.right_to_left)
+        .allow_rename(true)
+        .search_popup(true)
+        .context_menu(|ui, ids| {
+            if ids.is_empty() {
+                ui.label("tree:");
+                ui.label("No node selected");
+            } else {
+                ui.label("nodes:");
+                ui.label(format!("{} selected", ids.len()));
+            }
+        })
         .show(ui, |mut builder| {
             builder.node(NodeBuilder::dir(Uuid::default()).flatten(true));
             //builder.set_root_id(Uuid::default());
@@ -155,6 +206,22 @@ fn show_tree_view(ui: &mut Ui, app: &mut MyApp) -> Response {
                 }
             }
             Action::Drag { .. } => (),
+            Action::Rename { id, new_name } => {
+                app.tree.find_mut(id, &mut |node| match node {
+                    Node::Directory(dir) => dir.name = new_name.clone(),
+                    Node::File(file) => file.name = new_name.clone(),
+                });
+            }
+            Action::Delete(ids) => {
+                for id in ids {
+                    app.tree.remove(id);
+                }
+            }
+            Action::Cut(_)
+            | Action::Copy(_)
+            | Action::Paste { .. }
+            | Action::Duplicate(_)
+            | Action::SortBy { .. } => (),
         }
     }
     if app.settings.show_size {
@@ -172,12 +239,16 @@ fn show_node(builder: &mut TreeViewBuilder<Uuid>, node: &Node) {
 }
 fn show_dir(builder: &mut TreeViewBuilder<Uuid>, dir: &Directory) {
     let mut node = NodeBuilder::dir(dir.id)
+        .text(&dir.name)
         .label(|ui| {
             ui.add(Label::new(&dir.name).selectable(false));
         })
         .context_menu(|ui| {
             ui.label("dir:");
             ui.label(&dir.name);
+        })
+        .trailing(|ui| {
+            ui.add(Label::new(dir.children.len().to_string()).selectable(false));
         });
     if dir.icon {
         node = node.icon(|ui| {
@@ -214,13 +285,24 @@ fn show_dir(builder: &mut TreeViewBuilder<Uuid>, dir: &Directory) {
 }
 fn show_file(builder: &mut TreeViewBuilder<Uuid>, file: &File) {
     let mut node = NodeBuilder::leaf(file.id)
+        .text(&file.name)
         .label(|ui| {
             ui.add(Label::new(&file.name).selectable(false));
         })
         .context_menu(|ui| {
             ui.label("file:");
             ui.label(&file.name);
-        });
+        })
+        .tooltip_text(format!("File: {}", file.name))
+        .enabled(file.enabled);
+    if file.modified {
+        node = node.decoration(
+            Decoration::new(Color32::from_rgb(220, 180, 60))
+                .badge("M")
+                .dot(true)
+                .bubble(true),
+        );
+    }
     if file.icon {
         node = node.icon(|ui| {
             egui::Image::new(egui::include_image!("user.png"))
@@ -297,11 +379,17 @@ fn show_settings(ui: &mut Ui, settings: &mut Settings) {
                 VLineStyle::None => "None",
                 VLineStyle::VLine => "VLine",
                 VLineStyle::Hook => "Hook",
+                VLineStyle::FullHook => "FullHook",
+                VLineStyle::Dashed => "Dashed",
+                VLineStyle::Dotted => "Dotted",
             })
             .show_ui(ui, |ui| {
                 ui.selectable_value(&mut settings.vline_style, VLineStyle::None, "None");
                 ui.selectable_value(&mut settings.vline_style, VLineStyle::VLine, "VLine");
                 ui.selectable_value(&mut settings.vline_style, VLineStyle::Hook, "Hook");
+                ui.selectable_value(&mut settings.vline_style, VLineStyle::FullHook, "FullHook");
+                ui.selectable_value(&mut settings.vline_style, VLineStyle::Dashed, "Dashed");
+                ui.selectable_value(&mut settings.vline_style, VLineStyle::Dotted, "Dotted");
             });
         ui.end_row();
 
@@ -333,6 +421,24 @@ fn show_settings(ui: &mut Ui, settings: &mut Settings) {
             });
         ui.end_row();
 
+        ui.label("Density");
+        egui::ComboBox::from_id_salt("density combo box")
+            .selected_text(match settings.density {
+                RowDensity::Compact => "Compact",
+                RowDensity::Standard => "Standard",
+                RowDensity::Comfortable => "Comfortable",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut settings.density, RowDensity::Compact, "Compact");
+                ui.selectable_value(&mut settings.density, RowDensity::Standard, "Standard");
+                ui.selectable_value(
+                    &mut settings.density,
+                    RowDensity::Comfortable,
+                    "Comfortable",
+                );
+            });
+        ui.end_row();
+
         ui.label("fill horizontal");
         ui.checkbox(&mut settings.fill_space_horizontal, "");
         ui.end_row();
@@ -341,6 +447,34 @@ fn show_settings(ui: &mut Ui, settings: &mut Settings) {
         ui.checkbox(&mut settings.fill_space_vertical, "");
         ui.end_row();
 
+        ui.label("row hover highlight");
+        ui.checkbox(&mut settings.row_hover_highlight, "");
+        ui.end_row();
+
+        ui.label("striped");
+        ui.checkbox(&mut settings.striped, "");
+        ui.end_row();
+
+        ui.label("animate dirs");
+        ui.checkbox(&mut settings.animate_dirs, "");
+        ui.end_row();
+
+        ui.label("sticky headers");
+        ui.checkbox(&mut settings.sticky_headers, "");
+        ui.end_row();
+
+        ui.label("highlight active vline");
+        ui.checkbox(&mut settings.highlight_active_vline, "");
+        ui.end_row();
+
+        ui.label("default icons");
+        ui.checkbox(&mut settings.default_icons, "");
+        ui.end_row();
+
+        ui.label("right to left");
+        ui.checkbox(&mut settings.right_to_left, "");
+        ui.end_row();
+
         ui.label("max width");
         ui.horizontal(|ui| {
             ui.checkbox(&mut settings.max_width_enabled, "");
@@ -411,6 +545,14 @@ fn show_node_content(ui: &mut Ui, node: &mut Node) {
                 ui.label("Show icon");
                 ui.checkbox(&mut file.icon, "");
                 ui.end_row();
+
+                ui.label("Enabled");
+                ui.checkbox(&mut file.enabled, "");
+                ui.end_row();
+
+                ui.label("Modified");
+                ui.checkbox(&mut file.modified, "");
+                ui.end_row();
             }
         }
     });
@@ -0,0 +1,95 @@
+use egui::{Id, InnerResponse, SelectableLabel, Ui};
+
+use crate::{NodeId, TreeViewId, TreeViewState};
+
+/// A breadcrumb bar that shows the path from the root to the selected node of a tree view.
+///
+/// Each crumb shows the text of the node set with
+/// [`NodeBuilder::text`](crate::node::NodeBuilder::text). Nodes without a text are skipped.
+/// Clicking a crumb selects that node. The arrow in front of a crumb opens a dropdown
+/// of its siblings.
+pub struct TreeBreadcrumbs {
+    id: Id,
+}
+
+impl TreeBreadcrumbs {
+    /// Create breadcrumbs for the tree view with the given id.
+    pub fn new(tree_id: Id) -> Self {
+        Self { id: tree_id }
+    }
+
+    /// Show the breadcrumbs of the tree view.
+    ///
+    /// The state of the tree view is loaded from and stored to egui memory.
+    /// The inner value is the node that was selected using the breadcrumbs.
+    pub fn show<NodeIdType: NodeId>(self, ui: &mut Ui) -> InnerResponse<Option<NodeIdType>> {
+        let id = self.id;
+        let mut state = TreeViewState::load(ui, id).unwrap_or_default();
+        let res = self.show_state(ui, &mut state);
+        state.store(ui, id);
+        res
+    }
+
+    /// Show the breadcrumbs of the tree view with a [`TreeViewState`].
+    ///
+    /// The inner value is the node that was selected using the breadcrumbs.
+    pub fn show_state<NodeIdType: TreeViewId>(
+        self,
+        ui: &mut Ui,
+        state: &mut TreeViewState<NodeIdType>,
+    ) -> InnerResponse<Option<NodeIdType>> {
+        let mut path = Vec::new();
+        let mut current_node = state.selected;
+        while let Some(id) = current_node {
+            path.push(id);
+            current_node = state.parent_id_of(id);
+        }
+        path.reverse();
+
+        let mut picked = None;
+        let mut response = ui
+            .horizontal(|ui| {
+                for id in path.iter() {
+                    let Some(node_state) = state.node_state_of(id) else {
+                        continue;
+                    };
+                    let Some(text) = node_state.text.as_ref() else {
+                        continue;
+                    };
+                    let parent_id = state.parent_id_of(*id);
+                    ui.menu_button("⏵", |ui| {
+                        let siblings = state.node_states.iter().filter(|node_state| {
+                            node_state.parent_id == parent_id && node_state.enabled
+                        });
+                        for sibling in siblings {
+                            let Some(text) = sibling.text.as_ref() else {
+                                continue;
+                            };
+                            if ui.selectable_label(sibling.id == *id, text).clicked() {
+                                picked = Some(sibling.id);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    let is_selected = state.selected == Some(*id);
+                    // Disabled ancestors are shown but cannot be selected.
+                    if ui
+                        .add_enabled(node_state.enabled, SelectableLabel::new(is_selected, text))
+                        .clicked()
+                    {
+                        picked = Some(*id);
+                    }
+                }
+            })
+            .response;
+
+        let picked = picked.filter(|id| state.selected != Some(*id));
+        if let Some(id) = picked {
+            state.expand_parents_of(id, false);
+            state.selected = Some(id);
+            state.scroll_to_selected = true;
+            response.mark_changed();
+        }
+        InnerResponse::new(picked, response)
+    }
+}
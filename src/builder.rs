@@ -1,20 +1,33 @@
 use egui::{
     epaint::{self, RectShape},
     layers::ShapeIdx,
-    pos2, vec2, Pos2, Rangef, Rect, Shape, Stroke, Ui, WidgetText,
+    pos2,
+    text::{CCursor, CCursorRange, TextFormat},
+    text_edit::TextEditState,
+    vec2, FontSelection, Id, Key, Pos2, Rangef, Rect, Shape, Stroke, Ui, UiBuilder, WidgetText,
 };
 
 use crate::{
+    decoration::{dot_position, paint_dot},
     node::{DropQuarter, NodeBuilder},
-    DragState, DropPosition, NodeState, TreeViewData, TreeViewId, TreeViewSettings, VLineStyle,
+    open_context_menu,
+    search::{highlight_layout_job, match_ranges},
+    sticky::StickyRow,
+    Action, DragState, DropPosition, Interaction, NodeState, TreeViewData, TreeViewId,
+    TreeViewSettings, TreeViewStyle, VLineStyle,
 };
 
-#[derive(Clone)]
 struct DirectoryState<NodeIdType> {
     /// Id of the directory node.
     id: NodeIdType,
     /// If directory is expanded
     is_open: bool,
+    /// If the children of the directory are shown.
+    /// This is also true while the directory is animating closed.
+    is_shown: bool,
+    /// The ui of the parent and the openness of the directory while it is animating.
+    /// The children are shown in a clipped child ui until the directory is closed.
+    animation: Option<(Ui, f32)>,
     /// Wether dropping on this or any of its child nodes is allowed.
     drop_forbidden: bool,
     /// The rectangle of the row.
@@ -27,6 +40,10 @@ struct DirectoryState<NodeIdType> {
     indent_level: usize,
     /// If this dir was flattened.
     flattened: bool,
+    /// If the selected node is a direct child of this directory.
+    contains_selected: bool,
+    /// Style of the directory node.
+    style: TreeViewStyle,
 }
 
 /// The builder used to construct the tree view.
@@ -37,7 +54,9 @@ pub struct TreeViewBuilder<'ui, 'state, NodeIdType> {
     data: &'ui mut TreeViewData<'state, NodeIdType>,
     stack: Vec<DirectoryState<NodeIdType>>,
     background_idx: ShapeIdx,
-    secondary_selection_idx: ShapeIdx,
+    hover_idx: ShapeIdx,
+    /// Index of the next visible row.
+    row_index: usize,
     settings: &'ui TreeViewSettings,
 }
 
@@ -48,11 +67,12 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         settings: &'ui TreeViewSettings,
     ) -> Self {
         Self {
+            hover_idx: ui.painter().add(Shape::Noop),
             background_idx: ui.painter().add(Shape::Noop),
-            secondary_selection_idx: ui.painter().add(Shape::Noop),
             ui,
             data: state,
             stack: Vec::new(),
+            row_index: 0,
             settings,
         }
     }
@@ -62,54 +82,58 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         self.parent_dir().map(|state| state.id)
     }
 
+    /// Get the style of the tree view.
+    pub fn style(&self) -> &TreeViewStyle {
+        &self.data.style
+    }
+
     /// Add a leaf to the tree.
     pub fn leaf(&mut self, id: NodeIdType, label: impl Into<WidgetText>) {
         let widget_text = label.into();
-        self.node(NodeBuilder::leaf(id).label(|ui| {
-            ui.add(egui::Label::new(widget_text.clone()).selectable(false));
-        }));
+        let settings = self.settings;
+        let style = self.data.style;
+        self.node(
+            NodeBuilder::leaf(id)
+                .text(widget_text.text())
+                .label(|ui| add_label(ui, &widget_text, settings, &style)),
+        );
     }
 
     /// Add a directory to the tree.
     /// Must call [Self::close_dir] to close the directory.
     pub fn dir(&mut self, id: NodeIdType, label: impl Into<WidgetText>) {
         let widget_text = label.into();
-        self.node(NodeBuilder::dir(id).label(|ui| {
-            ui.add(egui::Label::new(widget_text.clone()).selectable(false));
-        }));
+        let settings = self.settings;
+        let style = self.data.style;
+        self.node(
+            NodeBuilder::dir(id)
+                .text(widget_text.text())
+                .label(|ui| add_label(ui, &widget_text, settings, &style)),
+        );
     }
 
     /// Close the current directory.
     pub fn close_dir(&mut self) {
-        let Some(current_dir) = self.stack.pop() else {
+        let Some(mut current_dir) = self.stack.pop() else {
             return;
         };
 
-        // Draw the drop marker over the entire dir if it is the target.
-        if let Some((drop_parent, DropPosition::Last)) = &self.data.drop {
-            if drop_parent == &current_dir.id {
-                let mut rect = current_dir.row_rect;
-                *rect.bottom_mut() =
-                    self.ui.cursor().top() - self.ui.spacing().item_spacing.y * 0.5;
-                self.ui.painter().set(
-                    self.data.drop_marker_idx,
-                    RectShape::new(
-                        rect,
-                        self.ui.visuals().widgets.active.rounding,
-                        self.ui.visuals().selection.bg_fill.linear_multiply(0.5),
-                        Stroke::NONE,
-                    ),
-                );
-            }
-        }
-
         // Draw vline
-        if current_dir.is_open {
+        if current_dir.is_shown {
             let top = current_dir.icon_rect.center_bottom() + vec2(0.0, 2.0);
+            // The vline of the directory that contains the selected node is highlighted.
+            let stroke = if self.settings.highlight_active_vline && current_dir.contains_selected {
+                current_dir.style.vline_active_stroke
+            } else {
+                current_dir.style.vline_stroke
+            };
 
             let bottom = match self.settings.vline_style {
                 VLineStyle::None => top,
-                VLineStyle::VLine => pos2(
+                VLineStyle::VLine
+                | VLineStyle::FullHook
+                | VLineStyle::Dashed
+                | VLineStyle::Dotted => pos2(
                     top.x,
                     self.ui.cursor().min.y - self.ui.spacing().item_spacing.y,
                 ),
@@ -122,19 +146,53 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
                         .unwrap_or(top.y),
                 ),
             };
-            self.ui.painter().line_segment(
-                [top, bottom],
-                self.ui.visuals().widgets.noninteractive.bg_stroke,
-            );
-            if matches!(self.settings.vline_style, VLineStyle::Hook) {
+            let mut shapes = self.vline_shapes([top, bottom], stroke);
+            if matches!(
+                self.settings.vline_style,
+                VLineStyle::Hook | VLineStyle::FullHook
+            ) {
+                // Leave a small gap between the hook and the node.
+                let gap = if self.settings.right_to_left {
+                    2.0
+                } else {
+                    -2.0
+                };
                 for child_pos in current_dir.child_node_positions.iter() {
                     let p1 = pos2(top.x, child_pos.y);
-                    let p2 = *child_pos + vec2(-2.0, 0.0);
-                    self.ui
-                        .painter()
-                        .line_segment([p1, p2], self.ui.visuals().widgets.noninteractive.bg_stroke);
+                    let p2 = *child_pos + vec2(gap, 0.0);
+                    shapes.push(Shape::line_segment([p1, p2], stroke));
                 }
             }
+            self.ui.painter().extend(shapes);
+        }
+
+        if let Some((parent_ui, openness)) = current_dir.animation.take() {
+            self.end_dir_animation(current_dir.id, parent_ui, openness);
+        }
+
+        if self.settings.sticky_headers {
+            self.data.sticky_bottoms.insert(
+                current_dir.id,
+                self.ui.cursor().top() - self.ui.spacing().item_spacing.y * 0.5,
+            );
+        }
+
+        // Draw the drop marker over the entire dir if it is the target.
+        if let Some((drop_parent, DropPosition::Last)) = &self.data.drop {
+            if drop_parent == &current_dir.id {
+                let mut rect = current_dir.row_rect;
+                *rect.bottom_mut() =
+                    self.ui.cursor().top() - self.ui.spacing().item_spacing.y * 0.5;
+                self.ui.painter().set(
+                    self.data.drop_marker_idx,
+                    RectShape::new(
+                        rect,
+                        current_dir.style.rounding,
+                        current_dir.style.drop_target_fill,
+                        Stroke::NONE,
+                    ),
+                );
+            }
         }
 
         // Add child markers to next dir if this one was flattened.
@@ -143,6 +201,7 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
                 parent_dir
                     .child_node_positions
                     .extend(current_dir.child_node_positions);
+                parent_dir.contains_selected |= current_dir.contains_selected;
             }
         }
     }
@@ -155,21 +214,44 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
             .node_state_of(&node.id)
             .map(|node_state| node_state.open)
             .unwrap_or(node.default_open);
+        // Directories that contain a node matching the filter are expanded
+        // without changing their stored open state.
+        let filter_open = self.data.contains_filter_match(&node.id);
+        let passes_filter = self.data.passes_filter(&node.id);
+        let visible = self.parent_dir_is_open() && !node.flatten && passes_filter;
+        // Nodes in a directory that is animating closed are still shown but are not visible.
+        let shown = self.parent_dir_is_shown() && !node.flatten && passes_filter;
+
+        if let Some(decoration) = node.decoration.as_ref().filter(|d| d.bubble) {
+            self.data
+                .bubbling_decorations
+                .push((node.id, decoration.color));
+        }
 
-        let (row, closer) = if self.parent_dir_is_open() && !node.flatten {
-            node.set_is_open(open);
-            let (row, closer) = self.node_internal(&mut node);
+        // Nothing can be dropped into a disabled node.
+        if !node.enabled {
+            node.drop_allowed = false;
+        }
+
+        let (row, closer) = if shown {
+            node.set_is_open(open || filter_open);
+            let (row, closer, row_interaction) = self.node_internal(&mut node);
 
             if let Some(closer) = closer {
-                let closer_interaction = self.data.interact(&closer);
+                let closer_interaction = self.data.interact(&closer.intersect(self.ui.clip_rect()));
+                // Directories expanded by the filter cannot be closed so their
+                // stored open state is kept.
                 if closer_interaction.clicked {
-                    open = !open;
-                    self.data.peristant.selected = Some(node.id);
+                    if !filter_open {
+                        open = !open;
+                    }
+                    if node.enabled {
+                        self.data.peristant.selected = Some(node.id);
+                    }
                 }
             }
 
-            let row_interaction = self.data.interact(&row);
-            if row_interaction.double_clicked {
+            if row_interaction.double_clicked && !filter_open {
                 open = !open;
             }
             (row, closer)
@@ -177,18 +259,40 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
             (Rect::NOTHING, Some(Rect::NOTHING))
         };
 
+        if self.data.is_selected(&node.id) {
+            if let Some(parent_dir) = self.stack.last_mut() {
+                parent_dir.contains_selected = true;
+            }
+        }
+
         self.data.new_node_states.push(NodeState {
             id: node.id,
             parent_id: self.parent_id(),
             open,
-            visible: self.parent_dir_is_open() && !node.flatten,
+            visible,
+            drop_allowed: node.drop_allowed,
+            enabled: node.enabled,
+            text: node.text.clone(),
         });
 
         if node.is_dir {
+            let openness = if shown {
+                self.dir_openness(node.id, open || filter_open)
+            } else if open || filter_open {
+                1.0
+            } else {
+                0.0
+            };
+            let animation = (shown && openness > 0.0 && openness < 1.0)
+                .then(|| (self.begin_dir_animation(node.id, openness), openness));
             self.stack.push(DirectoryState {
-                is_open: self.parent_dir_is_open() && open,
+                is_open: self.parent_dir_is_open() && (open || filter_open) && passes_filter,
+                is_shown: self.parent_dir_is_shown() && openness > 0.0 && passes_filter,
+                animation,
                 id: node.id,
-                drop_forbidden: self.parent_dir_drop_forbidden() || self.data.is_dragged(&node.id),
+                drop_forbidden: self.parent_dir_drop_forbidden()
+                    || self.data.is_dragged(&node.id)
+                    || !node.enabled,
                 row_rect: row,
                 icon_rect: closer.expect("Closer response should be availabel for dirs"),
                 child_node_positions: Vec::new(),
@@ -198,52 +302,158 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
                     self.get_indent_level() + 1
                 },
                 flattened: node.flatten,
+                contains_selected: false,
+                style: node.effective_style(self.data),
             });
         }
     }
 
-    fn node_internal(&mut self, node: &mut NodeBuilder<NodeIdType>) -> (Rect, Option<Rect>) {
+    fn node_internal(
+        &mut self,
+        node: &mut NodeBuilder<NodeIdType>,
+    ) -> (Rect, Option<Rect>, Interaction) {
         node.set_indent(self.get_indent_level());
-        let (row, closer, icon, label) = self
+        let style = node.effective_style(self.data);
+        if self.data.is_renaming(&node.id) {
+            self.start_rename(node);
+        }
+        let (row, closer, icon, label, trailing) = self
             .ui
             .scope(|ui| {
+                if !node.enabled {
+                    ui.disable();
+                }
                 // Set the fg stroke colors here so that the ui added by the user
                 // has the correct colors when selected or focused.
-                let fg_stroke = if self.data.is_selected(&node.id) && self.data.has_focus {
-                    ui.visuals().selection.stroke
+                let selected_with_focus = self.data.is_selected(&node.id) && self.data.has_focus;
+                let fg_stroke = if selected_with_focus {
+                    style.selection_fg_stroke
                 } else if self.data.is_selected(&node.id) {
-                    ui.visuals().widgets.inactive.fg_stroke
+                    style.selection_fg_stroke_unfocused
                 } else {
-                    ui.visuals().widgets.noninteractive.fg_stroke
+                    style.fg_stroke
+                };
+                // The text color of the node is replaced by the selection color
+                // so that the text stays legible on the selection background.
+                let fg_stroke = match node.text_color {
+                    Some(color) if !selected_with_focus => Stroke::new(fg_stroke.width, color),
+                    _ => fg_stroke,
                 };
                 ui.visuals_mut().widgets.noninteractive.fg_stroke = fg_stroke;
                 ui.visuals_mut().widgets.inactive.fg_stroke = fg_stroke;
+                // Strong text uses the active fg stroke.
+                if selected_with_focus || node.text_color.is_some() {
+                    ui.visuals_mut().widgets.active.fg_stroke = fg_stroke;
+                }
 
                 node.show_node(ui, self.data, self.settings)
             })
             .inner;
 
-        let row_interaction = self.data.interact(&row);
+        if self.data.is_renaming(&node.id) {
+            self.finish_rename(node);
+        }
+
+        // Interacting with the trailing widgets should not interact with the row.
+        let pointer_on_trailing = trailing.is_some_and(|trailing| {
+            self.data
+                .interaction_response
+                .hover_pos()
+                .is_some_and(|pos| trailing.contains(pos))
+        });
+        let row_interaction = if pointer_on_trailing {
+            Interaction::default()
+        } else {
+            // Rows that are clipped away by an animating directory cannot be interacted with.
+            self.data.interact(&row.intersect(self.ui.clip_rect()))
+        };
+
+        // Paint the dot of the decoration or remember where to paint the dot
+        // of a decoration that bubbles up from a child.
+        if let Some(icon) = icon.or(closer) {
+            let pos = dot_position(icon, &style, self.settings.right_to_left);
+            match node.decoration.as_ref().filter(|d| d.dot) {
+                Some(decoration) => paint_dot(self.ui.painter(), pos, decoration.color, &style),
+                None if node.is_dir && self.ui.clip_rect().contains(pos) => {
+                    self.data.decoration_dots.insert(node.id, pos);
+                }
+                None => (),
+            }
+        }
+
+        if self.settings.sticky_headers {
+            self.data.sticky_rows.push(StickyRow {
+                id: node.id,
+                row,
+                closer,
+                label,
+            });
+        }
+
+        // Stripe every other visible row. The row backgrounds are painted after the tree
+        // is built, so they are clipped here for rows of an animating directory.
+        let background_rect = row.intersect(self.ui.clip_rect());
+        if self.settings.striped && self.row_index % 2 == 1 {
+            self.data
+                .row_backgrounds
+                .push((background_rect, style.stripe_bg_fill));
+        }
+        self.row_index += 1;
+        if let Some(background_color) = node.background_color {
+            self.data
+                .row_backgrounds
+                .push((background_rect, background_color));
+        }
+
+        // Highlight the hovered row. While dragging the drop marker shows where the pointer is.
+        if self.settings.row_hover_highlight
+            && node.enabled
+            && row_interaction.hovered
+            && !self.data.drag_valid()
+        {
+            self.ui.painter().set(
+                self.hover_idx,
+                epaint::RectShape::new(row, style.rounding, style.hover_bg_fill, Stroke::NONE),
+            );
+        }
 
         // React to primary clicking
-        if row_interaction.clicked {
+        if row_interaction.clicked && node.enabled {
+            // Clicking a node that is already selected starts a rename.
+            // Clicking the closer only opens or closes the directory.
+            let closer_clicked = closer.is_some_and(|closer| {
+                self.data
+                    .interact(&closer.intersect(self.ui.clip_rect()))
+                    .clicked
+            });
+            if self.settings.allow_rename
+                && self.data.has_focus
+                && self.data.is_selected(&node.id)
+                && !row_interaction.double_clicked
+                && !closer_clicked
+            {
+                let time = self.ui.input(|i| i.time);
+                self.data.peristant.rename_click = Some((node.id, time));
+            }
             self.data.peristant.selected = Some(node.id);
         }
+        if row_interaction.double_clicked {
+            self.data.peristant.rename_click = None;
+        }
         if self.data.is_selected(&node.id) {
+            if self.data.peristant.scroll_to_selected {
+                self.data.peristant.scroll_to_selected = false;
+                self.ui.scroll_to_rect(row, None);
+            }
             self.ui.painter().set(
                 self.background_idx,
                 epaint::RectShape::new(
                     row,
-                    self.ui.visuals().widgets.active.rounding,
+                    style.rounding,
                     if self.data.has_focus {
-                        self.ui.visuals().selection.bg_fill
+                        style.selection_bg_fill
                     } else {
-                        self.ui
-                            .visuals()
-                            .widgets
-                            .inactive
-                            .weak_bg_fill
-                            .linear_multiply(0.3)
+                        style.selection_bg_fill_unfocused
                     },
                     Stroke::NONE,
                 ),
@@ -259,11 +469,19 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         let primary_pressed = self
             .ui
             .input(|i| i.pointer.button_pressed(egui::PointerButton::Primary));
-        if row_interaction.hovered && primary_pressed {
+        let drag_allowed = self.settings.allow_drag
+            && node.drag_allowed
+            && node.enabled
+            && !self.data.is_renaming(&node.id);
+        if row_interaction.hovered && primary_pressed && drag_allowed {
             let pointer_pos = self.ui.ctx().pointer_latest_pos().unwrap_or_default();
             self.data.peristant.dragged = Some(DragState {
                 node_id: node.id,
-                drag_row_offset: row.min - pointer_pos,
+                drag_row_offset: if self.settings.right_to_left {
+                    row.right_top() - pointer_pos
+                } else {
+                    row.left_top() - pointer_pos
+                },
                 drag_start_pos: pointer_pos,
                 drag_valid: false,
             });
@@ -272,34 +490,109 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
             node.show_node_dragged(self.ui, self.data, self.settings);
         }
 
+        // Show the tooltip while the row is hovered.
+        if row_interaction.hovered && self.data.peristant.dragged.is_none() {
+            node.show_tooltip(&self.data.interaction_response);
+        }
+
         // React to secondary clicks
         if row_interaction.secondary_clicked {
             self.data.peristant.secondary_selection = Some(node.id);
         }
         if self.data.is_secondary_selected(&node.id) {
+            if self.data.peristant.open_context_menu {
+                self.data.peristant.open_context_menu = false;
+                open_context_menu(&self.data.interaction_response, row.left_bottom());
+            }
+            if !node.has_context_menu() {
+                // The context menu of the tree view is shown for this node instead.
+                self.data.secondary_selection_row = Some(row);
+            }
             let context_menu_visible = node.show_context_menu(&self.data.interaction_response);
 
             if !self.data.is_selected(&node.id) && context_menu_visible {
                 self.ui.painter().set(
-                    self.secondary_selection_idx,
+                    self.data.secondary_selection_idx,
                     epaint::RectShape::new(
                         row,
-                        self.ui.visuals().widgets.active.rounding,
+                        style.rounding,
                         egui::Color32::TRANSPARENT,
-                        self.ui.visuals().widgets.inactive.fg_stroke,
+                        style.secondary_selection_stroke,
                     ),
                 );
             }
         }
 
-        self.do_drop_node(node, &row);
+        self.do_drop_node(node, &row, &style);
 
-        self.push_child_node_position(closer.or(icon).unwrap_or(label).left_center());
+        // The hooks of the vline point to the side of the node the tree starts from.
+        let node_rect = closer.or(icon).unwrap_or(label);
+        self.push_child_node_position(if self.settings.right_to_left {
+            node_rect.right_center()
+        } else {
+            node_rect.left_center()
+        });
 
-        (row, closer)
+        (row, closer, row_interaction)
     }
 
-    fn do_drop_node(&mut self, node: &NodeBuilder<NodeIdType>, row: &Rect) {
+    /// Fill the rename text edit with the text of the node and focus it.
+    fn start_rename(&mut self, node: &NodeBuilder<NodeIdType>) {
+        let Some(rename_state) = self.data.peristant.renaming.as_mut() else {
+            return;
+        };
+        if rename_state.initialized {
+            return;
+        }
+        let Some(text) = node.text.clone() else {
+            // Nodes without a text cannot be renamed.
+            self.data.peristant.renaming = None;
+            return;
+        };
+        rename_state.initialized = true;
+
+        let edit_id = self.data.rename_edit_id();
+        let mut edit_state = TextEditState::load(self.ui.ctx(), edit_id).unwrap_or_default();
+        edit_state.cursor.set_char_range(Some(CCursorRange::two(
+            CCursor::new(0),
+            CCursor::new(text.chars().count()),
+        )));
+        edit_state.store(self.ui.ctx(), edit_id);
+        self.ui.data_mut(|d| d.insert_temp(edit_id, text));
+        self.ui.memory_mut(|m| m.request_focus(edit_id));
+    }
+
+    /// Commit or cancel the rename once the text edit lost focus.
+    fn finish_rename(&mut self, node: &NodeBuilder<NodeIdType>) {
+        let edit_id = self.data.rename_edit_id();
+        let lost_focus = self
+            .ui
+            .memory(|m| m.had_focus_last_frame(edit_id) && !m.has_focus(edit_id));
+        if !lost_focus {
+            return;
+        }
+
+        let new_name = self
+            .ui
+            .data_mut(|d| d.remove_temp::<String>(edit_id))
+            .unwrap_or_default();
+        let (enter_pressed, escape_pressed) = self
+            .ui
+            .input(|i| (i.key_pressed(Key::Enter), i.key_pressed(Key::Escape)));
+        if !escape_pressed {
+            self.data.actions.push(Action::Rename {
+                id: node.id,
+                new_name,
+            });
+        }
+        // Return the focus to the tree if the rename was ended by the keyboard.
+        if enter_pressed || escape_pressed {
+            self.ui.memory_mut(|m| m.request_focus(self.data.id));
+        }
+        self.data.peristant.renaming = None;
+    }
+
+    fn do_drop_node(&mut self, node: &NodeBuilder<NodeIdType>, row: &Rect, style: &TreeViewStyle) {
         let Some(drop_quarter) = self
             .data
             .interaction_response
@@ -325,7 +618,7 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         }
 
         let drop_position = self.get_drop_position_node(node, &drop_quarter);
-        let shape = self.drop_marker_shape(row, drop_position.as_ref());
+        let shape = drop_marker_shape(row, drop_position.as_ref(), style);
 
         // It is allowed to drop itself `After´ or `Before` itself.
         // This however doesn't make sense and makes executing the command more
@@ -395,36 +688,19 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         }
     }
 
-    fn drop_marker_shape(
-        &self,
-        interaction: &Rect,
-        drop_position: Option<&(NodeIdType, DropPosition<NodeIdType>)>,
-    ) -> Shape {
-        pub const DROP_LINE_HEIGHT: f32 = 3.0;
-
-        let drop_marker = match drop_position {
-            Some((_, DropPosition::Before(_))) => {
-                Rangef::point(interaction.min.y).expand(DROP_LINE_HEIGHT * 0.5)
-            }
-            Some((_, DropPosition::First)) | Some((_, DropPosition::After(_))) => {
-                Rangef::point(interaction.max.y).expand(DROP_LINE_HEIGHT * 0.5)
-            }
-            Some((_, DropPosition::Last)) => interaction.y_range(),
-            None => return Shape::Noop,
-        };
+    /// Create the shapes of a vertical line in the vline style of the tree.
+    fn vline_shapes(&self, points: [Pos2; 2], stroke: Stroke) -> Vec<Shape> {
+        const DASH_LENGTH: f32 = 4.0;
+        const GAP_LENGTH: f32 = 3.0;
+        const DOT_SPACING: f32 = 3.0;
 
-        epaint::RectShape::new(
-            Rect::from_x_y_ranges(interaction.x_range(), drop_marker),
-            self.ui.visuals().widgets.active.rounding,
-            self.ui
-                .style()
-                .visuals
-                .selection
-                .bg_fill
-                .linear_multiply(0.6),
-            Stroke::NONE,
-        )
-        .into()
+        match self.settings.vline_style {
+            VLineStyle::Dashed => Shape::dashed_line(&points, stroke, DASH_LENGTH, GAP_LENGTH),
+            VLineStyle::Dotted => {
+                Shape::dotted_line(&points, stroke.color, DOT_SPACING, stroke.width * 0.5)
+            }
+            _ => vec![Shape::line_segment(points, stroke)],
+        }
     }
 
     fn parent_dir(&self) -> Option<&DirectoryState<NodeIdType>> {
@@ -435,13 +711,67 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         }
     }
     fn parent_dir_is_open(&self) -> bool {
-        self.parent_dir().map_or(true, |dir| dir.is_open)
+        self.parent_dir().is_none_or(|dir| dir.is_open)
+    }
+
+    fn parent_dir_is_shown(&self) -> bool {
+        self.parent_dir().is_none_or(|dir| dir.is_shown)
     }
 
     fn parent_dir_drop_forbidden(&self) -> bool {
         self.parent_dir().is_some_and(|dir| dir.drop_forbidden)
     }
 
+    /// Get how far a directory is opened.
+    ///
+    /// Returns a value between `0.0` for closed and `1.0` for open.
+    fn dir_openness(&self, id: NodeIdType, open: bool) -> f32 {
+        if self.settings.animate_dirs {
+            self.ui.ctx().animate_bool(self.dir_animation_id(id), open)
+        } else if open {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn dir_animation_id(&self, id: NodeIdType) -> Id {
+        self.data.id.with(id).with("dir animation")
+    }
+
+    /// Show the following nodes in a child ui that is clipped to the animated height
+    /// of the directory.
+    ///
+    /// Returns the ui of the parent which has to be restored once the directory is closed.
+    fn begin_dir_animation(&mut self, id: NodeIdType, openness: f32) -> Ui {
+        let full_height = self
+            .ui
+            .data(|d| d.get_temp::<f32>(self.dir_animation_id(id)))
+            .unwrap_or(0.0);
+        let mut child_ui = self.ui.new_child(
+            UiBuilder::new()
+                .max_rect(self.ui.available_rect_before_wrap())
+                .layout(*self.ui.layout()),
+        );
+        let top = child_ui.cursor().top();
+        let clip_rect = child_ui.clip_rect().intersect(Rect::from_x_y_ranges(
+            Rangef::EVERYTHING,
+            Rangef::new(top, top + full_height * openness),
+        ));
+        child_ui.set_clip_rect(clip_rect);
+        std::mem::replace(self.ui, child_ui)
+    }
+
+    /// Restore the ui of the parent and allocate the animated height of the directory.
+    fn end_dir_animation(&mut self, id: NodeIdType, parent_ui: Ui, openness: f32) {
+        let child_ui = std::mem::replace(self.ui, parent_ui);
+        // The height of the children if the directory was fully open.
+        let full_height = child_ui.cursor().top() - child_ui.max_rect().top();
+        self.ui
+            .data_mut(|d| d.insert_temp(self.dir_animation_id(id), full_height));
+        self.ui.add_space(full_height * openness);
+    }
+
     fn push_child_node_position(&mut self, pos: Pos2) {
         if let Some(parent_dir) = self.stack.last_mut() {
             parent_dir.child_node_positions.push(pos);
@@ -451,3 +781,69 @@ impl<'ui, 'state, NodeIdType: TreeViewId> TreeViewBuilder<'ui, 'state, NodeIdTyp
         self.stack.last().map(|d| d.indent_level).unwrap_or(0)
     }
 }
+
+impl<NodeIdType> Drop for TreeViewBuilder<'_, '_, NodeIdType> {
+    fn drop(&mut self) {
+        // Restore the ui of the tree if a directory was left open while animating.
+        while let Some(dir) = self.stack.pop() {
+            if let Some((parent_ui, _)) = dir.animation {
+                *self.ui = parent_ui;
+            }
+        }
+    }
+}
+
+fn drop_marker_shape<NodeIdType>(
+    interaction: &Rect,
+    drop_position: Option<&(NodeIdType, DropPosition<NodeIdType>)>,
+    style: &TreeViewStyle,
+) -> Shape {
+    let drop_marker = match drop_position {
+        Some((_, DropPosition::Before(_))) => {
+            Rangef::point(interaction.min.y).expand(style.drop_marker_height * 0.5)
+        }
+        Some((_, DropPosition::First)) | Some((_, DropPosition::After(_))) => {
+            Rangef::point(interaction.max.y).expand(style.drop_marker_height * 0.5)
+        }
+        Some((_, DropPosition::Last)) => interaction.y_range(),
+        None => return Shape::Noop,
+    };
+
+    epaint::RectShape::new(
+        Rect::from_x_y_ranges(interaction.x_range(), drop_marker),
+        style.rounding,
+        style.drop_marker_fill,
+        Stroke::NONE,
+    )
+    .into()
+}
+
+/// Add the label of a leaf or directory.
+///
+/// The parts of the text that match the search query are highlighted.
+pub(crate) fn add_label(
+    ui: &mut Ui,
+    widget_text: &WidgetText,
+    settings: &TreeViewSettings,
+    style: &TreeViewStyle,
+) {
+    let highlights = settings
+        .search_query
+        .as_ref()
+        .and_then(|query| match_ranges(widget_text.text(), query, settings.search_mode));
+    let Some(highlights) = highlights else {
+        ui.add(egui::Label::new(widget_text.clone()).selectable(false));
+        return;
+    };
+
+    let layout_job =
+        widget_text
+            .clone()
+            .into_layout_job(ui.style(), FontSelection::Default, ui.text_valign());
+    let layout_job = highlight_layout_job(layout_job, &highlights, |format| TextFormat {
+        background: style.search_highlight_fill,
+        underline: style.search_highlight_stroke,
+        ..format.clone()
+    });
+    ui.add(egui::Label::new(layout_job).selectable(false));
+}
@@ -0,0 +1,231 @@
+use egui::{
+    pos2, vec2, CursorIcon, Label, Layout, NumExt, Rangef, Rect, Sense, Shape, Stroke, Ui,
+    UiBuilder, Vec2, WidgetText,
+};
+
+use crate::{Action, TreeViewData, TreeViewId, TreeViewSettings};
+
+/// A column of the tree view.
+///
+/// Columns are shown to the right of the tree column. Use
+/// [`NodeBuilder::column`](crate::node::NodeBuilder::column) to add the content of
+/// a column to a node.
+pub struct Column {
+    pub(crate) header: WidgetText,
+    pub(crate) width: f32,
+    pub(crate) min_width: f32,
+    pub(crate) resizable: bool,
+    pub(crate) sortable: bool,
+}
+impl Column {
+    /// Create a new column with a header.
+    pub fn new(header: impl Into<WidgetText>) -> Self {
+        Self {
+            header: header.into(),
+            width: 100.0,
+            min_width: 20.0,
+            resizable: true,
+            sortable: false,
+        }
+    }
+
+    /// Set the initial width of the column.
+    ///
+    /// Default is `100.0`.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the minimum width the column can be resized to.
+    ///
+    /// Default is `20.0`.
+    pub fn min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Set whether or not the column can be resized by dragging its left edge.
+    ///
+    /// Default is `true`.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Set whether or not the tree can be sorted by this column.
+    ///
+    /// Clicking the header of a sortable column cycles through ascending,
+    /// descending and no sorting and creates an [`Action::SortBy`].
+    ///
+    /// Default is `false`.
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// A column the tree can be sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortColumn {
+    /// The tree column.
+    Tree,
+    /// An additional column with the index in the order the columns were added.
+    Column(usize),
+}
+
+/// The direction in which the tree is sorted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+/// Show the header row of the columns.
+pub(crate) fn show_column_headers<NodeIdType: TreeViewId>(
+    ui: &mut Ui,
+    data: &mut TreeViewData<NodeIdType>,
+    settings: &TreeViewSettings,
+) {
+    const RESIZE_HANDLE_WIDTH: f32 = 6.0;
+
+    let (rect, _) = ui.allocate_exact_size(
+        vec2(ui.available_width(), ui.spacing().interact_size.y),
+        Sense::hover(),
+    );
+    let (tree_column, column_ranges) = data.column_ranges(rect.x_range(), settings.right_to_left);
+
+    let headers =
+        std::iter::once((
+            SortColumn::Tree,
+            &settings.tree_column_header,
+            settings.tree_column_sortable,
+        ))
+        .chain(
+            settings.columns.iter().enumerate().map(|(index, column)| {
+                (SortColumn::Column(index), &column.header, column.sortable)
+            }),
+        );
+    let ranges = std::iter::once(tree_column).chain(column_ranges.iter().copied());
+    for ((sort_column, header, sortable), range) in headers.zip(ranges) {
+        let cell_rect = Rect::from_x_y_ranges(range, rect.y_range());
+        let mut header_rect = cell_rect.shrink2(vec2(ui.spacing().item_spacing.x, 0.0));
+
+        if sortable {
+            let response = ui.interact(
+                cell_rect,
+                data.id.with("column header").with(sort_column),
+                Sense::click(),
+            );
+            if response.hovered() {
+                ui.ctx().set_cursor_icon(CursorIcon::PointingHand);
+                ui.painter()
+                    .rect_filled(cell_rect, 0.0, ui.visuals().widgets.hovered.weak_bg_fill);
+            }
+            if response.clicked() {
+                let direction = match data.peristant.sort {
+                    Some((column, SortDirection::Ascending)) if column == sort_column => {
+                        Some(SortDirection::Descending)
+                    }
+                    Some((column, SortDirection::Descending)) if column == sort_column => None,
+                    _ => Some(SortDirection::Ascending),
+                };
+                data.peristant.sort = direction.map(|direction| (sort_column, direction));
+                data.actions.push(Action::SortBy {
+                    column: sort_column,
+                    direction,
+                });
+            }
+
+            // Show the sort direction on the trailing side of the header.
+            if let Some((_, direction)) = data
+                .peristant
+                .sort
+                .filter(|(column, _)| *column == sort_column)
+            {
+                let indicator_size = ui.spacing().icon_width * 0.5;
+                let indicator_x = if settings.right_to_left {
+                    header_rect.left() + indicator_size * 0.5
+                } else {
+                    header_rect.right() - indicator_size * 0.5
+                };
+                let indicator_rect = Rect::from_center_size(
+                    pos2(indicator_x, header_rect.center().y),
+                    Vec2::splat(indicator_size),
+                );
+                let points = match direction {
+                    SortDirection::Ascending => vec![
+                        indicator_rect.left_bottom(),
+                        indicator_rect.right_bottom(),
+                        indicator_rect.center_top(),
+                    ],
+                    SortDirection::Descending => vec![
+                        indicator_rect.left_top(),
+                        indicator_rect.right_top(),
+                        indicator_rect.center_bottom(),
+                    ],
+                };
+                ui.painter().add(Shape::convex_polygon(
+                    points,
+                    ui.visuals().widgets.inactive.fg_stroke.color,
+                    Stroke::NONE,
+                ));
+                if settings.right_to_left {
+                    header_rect.min.x += indicator_size + ui.spacing().item_spacing.x;
+                } else {
+                    header_rect.max.x -= indicator_size + ui.spacing().item_spacing.x;
+                }
+            }
+        }
+
+        let layout = if settings.right_to_left {
+            Layout::right_to_left(egui::Align::Center)
+        } else {
+            Layout::left_to_right(egui::Align::Center)
+        };
+        let mut header_ui = ui.new_child(UiBuilder::new().max_rect(header_rect).layout(layout));
+        header_ui.set_clip_rect(header_ui.clip_rect().intersect(header_rect));
+        header_ui.add(Label::new(header.clone()).selectable(false).truncate());
+    }
+
+    ui.painter().hline(
+        rect.x_range(),
+        rect.bottom(),
+        ui.visuals().widgets.noninteractive.bg_stroke,
+    );
+
+    // Resize the columns by dragging the edge that faces the tree column.
+    for (index, (column, range)) in settings.columns.iter().zip(column_ranges).enumerate() {
+        let (edge, direction) = if settings.right_to_left {
+            (range.max, 1.0)
+        } else {
+            (range.min, -1.0)
+        };
+        let handle_rect = Rect::from_x_y_ranges(
+            Rangef::point(edge).expand(RESIZE_HANDLE_WIDTH * 0.5),
+            rect.y_range(),
+        );
+        let stroke = if column.resizable {
+            let response = ui.interact(
+                handle_rect,
+                data.id.with("column resize").with(index),
+                Sense::drag(),
+            );
+            if response.dragged() {
+                let width = &mut data.peristant.column_widths[index];
+                *width = (*width + direction * response.drag_delta().x).at_least(column.min_width);
+            }
+            if response.hovered() || response.dragged() {
+                ui.ctx().set_cursor_icon(CursorIcon::ResizeHorizontal);
+                ui.visuals().widgets.hovered.fg_stroke
+            } else {
+                ui.visuals().widgets.noninteractive.bg_stroke
+            }
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        ui.painter().vline(edge, rect.y_range(), stroke);
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use egui::{vec2, Color32, Painter, Pos2, Rect};
+
+use crate::{TreeViewData, TreeViewId, TreeViewStyle};
+
+/// A status decoration of a node, like the changes of a file in version control.
+///
+/// Use [`NodeBuilder::decoration`](crate::node::NodeBuilder::decoration) to add a
+/// decoration to a node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decoration {
+    pub(crate) color: Color32,
+    pub(crate) badge: Option<String>,
+    pub(crate) dot: bool,
+    pub(crate) bubble: bool,
+}
+impl Decoration {
+    /// Create a new decoration with a color.
+    pub fn new(color: Color32) -> Self {
+        Self {
+            color,
+            badge: None,
+            dot: false,
+            bubble: false,
+        }
+    }
+
+    /// Show a small badge with a text, like a letter or a number, at the end of the row.
+    ///
+    /// The badges are aligned to the end of the tree column so they line up across rows.
+    /// Trailing widgets of the node are shown before the badge.
+    pub fn badge(mut self, badge: impl Into<String>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    /// Set whether or not a colored dot is shown on the icon of the node.
+    ///
+    /// Default is `false`.
+    pub fn dot(mut self, dot: bool) -> Self {
+        self.dot = dot;
+        self
+    }
+
+    /// Set whether or not the decoration bubbles up to the ancestors of the node.
+    ///
+    /// The ancestor directories show a colored dot on their icon to indicate that
+    /// they contain a decorated node. This also works if the directories are collapsed
+    /// as long as the node is added to the tree.
+    ///
+    /// Default is `false`.
+    pub fn bubble(mut self, bubble: bool) -> Self {
+        self.bubble = bubble;
+        self
+    }
+}
+
+/// Get the position of the decoration dot on the icon of a node.
+///
+/// The dot sits in the bottom corner of the icon that faces the label.
+pub(crate) fn dot_position(icon: Rect, style: &TreeViewStyle, right_to_left: bool) -> Pos2 {
+    let radius = style.decoration_dot_radius;
+    if right_to_left {
+        icon.left_bottom() + vec2(radius, -radius)
+    } else {
+        icon.right_bottom() - vec2(radius, radius)
+    }
+}
+
+/// Paint the decoration dot of a node.
+pub(crate) fn paint_dot(painter: &Painter, pos: Pos2, color: Color32, style: &TreeViewStyle) {
+    painter.circle_filled(pos, style.decoration_dot_radius, color);
+}
+
+/// Paint the dots of the directories that contain a bubbling decoration.
+///
+/// Ancestors are found using the parent ids of the node states because the
+/// directories are shown before their children are added to the tree.
+pub(crate) fn show_bubbled_decorations<NodeIdType: TreeViewId>(
+    painter: &Painter,
+    data: &TreeViewData<NodeIdType>,
+) {
+    if data.bubbling_decorations.is_empty() {
+        return;
+    }
+    let parents = data
+        .new_node_states
+        .iter()
+        .map(|node_state| (node_state.id, node_state.parent_id))
+        .collect::<HashMap<_, _>>();
+
+    let mut bubbled = HashMap::new();
+    for (id, color) in data.bubbling_decorations.iter() {
+        let mut current_parent = parents.get(id).copied().flatten();
+        while let Some(parent_id) = current_parent {
+            bubbled.entry(parent_id).or_insert(*color);
+            current_parent = parents.get(&parent_id).copied().flatten();
+        }
+    }
+
+    for (id, color) in bubbled {
+        if let Some(pos) = data.decoration_dots.get(&id) {
+            paint_dot(painter, *pos, color, &data.style);
+        }
+    }
+}
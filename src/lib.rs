@@ -1,14 +1,34 @@
+pub mod breadcrumbs;
 pub mod builder;
+pub mod column;
+pub mod decoration;
 pub mod node;
+mod search;
+mod sticky;
+pub mod style;
 
-use std::hash::Hash;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
 use egui::{
-    self, layers::ShapeIdx, vec2, Event, EventFilter, Id, Key, Layout, NumExt, Pos2, Rect,
-    Response, Sense, Shape, Ui, Vec2,
+    self, epaint,
+    layers::ShapeIdx,
+    menu::{BarState, MenuResponse, MenuRoot},
+    vec2, Color32, Event, EventFilter, Id, Key, Layout, Modifiers, NumExt, Pos2, Rangef, Rect,
+    Response, Rounding, Sense, Shape, Ui, Vec2, WidgetText,
 };
 
+pub use breadcrumbs::TreeBreadcrumbs;
 pub use builder::TreeViewBuilder;
+use column::show_column_headers;
+pub use column::{Column, SortColumn, SortDirection};
+use decoration::show_bubbled_decorations;
+pub use decoration::Decoration;
+use search::show_search_popup;
+use sticky::{show_sticky_headers, StickyRow};
+pub use style::TreeViewStyle;
 
 pub trait TreeViewId: Clone + Copy + PartialEq + Eq + Hash {}
 impl<T> TreeViewId for T where T: Clone + Copy + PartialEq + Eq + Hash {}
@@ -42,10 +62,26 @@ pub struct TreeViewState<NodeIdType> {
     dragged: Option<DragState<NodeIdType>>,
     /// Id of the node that was right clicked.
     secondary_selection: Option<NodeIdType>,
+    /// Wether the context menu of the secondary selection should be opened
+    /// the next time the node is shown.
+    open_context_menu: bool,
     /// The rectangle the tree view occupied.
     size: Vec2,
     /// Open states of the dirs in this tree.
     node_states: Vec<NodeState<NodeIdType>>,
+    /// Widths of the additional columns.
+    column_widths: Vec<f32>,
+    /// The column and direction the tree is sorted by.
+    sort: Option<(SortColumn, SortDirection)>,
+    /// Information about the node that is being renamed.
+    renaming: Option<RenameState<NodeIdType>>,
+    /// State of the search popup if it is open.
+    search_popup: Option<SearchPopupState>,
+    /// Wether the tree should scroll to the selected node the next time it is shown.
+    scroll_to_selected: bool,
+    /// Id of the selected node that was clicked again and the time of the click.
+    /// If no double click follows, the node is renamed.
+    rename_click: Option<(NodeIdType, f64)>,
 }
 impl<NodeIdType> Default for TreeViewState<NodeIdType> {
     fn default() -> Self {
@@ -53,8 +89,15 @@ impl<NodeIdType> Default for TreeViewState<NodeIdType> {
             selected: Default::default(),
             dragged: Default::default(),
             secondary_selection: Default::default(),
+            open_context_menu: false,
             size: Vec2::ZERO,
             node_states: Vec::new(),
+            column_widths: Vec::new(),
+            sort: None,
+            renaming: None,
+            search_popup: None,
+            scroll_to_selected: false,
+            rename_click: None,
         }
     }
 }
@@ -89,6 +132,40 @@ impl<NodeIdType: TreeViewId> TreeViewState<NodeIdType> {
         }
     }
 
+    /// Return the column and direction the tree is sorted by if any.
+    pub fn sort(&self) -> Option<(SortColumn, SortDirection)> {
+        self.sort
+    }
+
+    /// Set the column and direction the tree is sorted by.
+    pub fn set_sort(&mut self, sort: Option<(SortColumn, SortDirection)>) {
+        self.sort = sort;
+    }
+
+    /// Scroll to the selected node the next time it is shown.
+    pub fn scroll_to_selected(&mut self) {
+        self.scroll_to_selected = true;
+    }
+
+    /// Return the node that is currently being renamed if any.
+    pub fn renaming(&self) -> Option<NodeIdType> {
+        self.renaming
+            .as_ref()
+            .map(|rename_state| rename_state.node_id)
+    }
+
+    /// Start renaming the node with the given id.
+    ///
+    /// The node must have a text set with [`node::NodeBuilder::text`]
+    /// and be shown the next time the tree is shown for the rename to start.
+    pub fn start_rename(&mut self, id: NodeIdType) {
+        self.rename_click = None;
+        self.renaming = Some(RenameState {
+            node_id: id,
+            initialized: false,
+        });
+    }
+
     /// Get the parent id of a node.
     pub fn parent_id_of(&self, id: NodeIdType) -> Option<NodeIdType> {
         self.node_state_of(&id)
@@ -134,6 +211,24 @@ struct DragState<NodeIdType> {
     /// a short distance.
     pub drag_valid: bool,
 }
+/// State of the node that is being renamed.
+#[derive(Clone)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+struct RenameState<NodeIdType> {
+    /// Id of the renamed node.
+    node_id: NodeIdType,
+    /// Wether the text edit was already filled with the text of the node.
+    initialized: bool,
+}
+/// State of the search popup.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
+struct SearchPopupState {
+    /// The search query.
+    query: String,
+    /// Index of the highlighted search result.
+    selected_index: usize,
+}
 /// State of each node in the tree.
 #[derive(Clone)]
 #[cfg_attr(feature = "persistence", derive(serde::Serialize, serde::Deserialize))]
@@ -146,17 +241,31 @@ struct NodeState<NodeIdType> {
     open: bool,
     /// Wether the node is visible or not.
     visible: bool,
+    /// Wether dropping onto this node is allowed.
+    drop_allowed: bool,
+    /// Wether the node can be selected and interacted with.
+    enabled: bool,
+    /// The text of the node if it has any.
+    text: Option<String>,
 }
 
-pub struct TreeView {
+pub type AddContextMenu<'tree, NodeIdType> = dyn FnMut(&mut Ui, &[NodeIdType]) + 'tree;
+
+pub type Filter<'tree, NodeIdType> = dyn Fn(&NodeIdType) -> bool + 'tree;
+
+pub struct TreeView<'tree, NodeIdType> {
     id: Id,
     settings: TreeViewSettings,
+    context_menu: Option<Box<AddContextMenu<'tree, NodeIdType>>>,
+    filter: Option<Box<Filter<'tree, NodeIdType>>>,
 }
-impl TreeView {
+impl<'tree, NodeIdType> TreeView<'tree, NodeIdType> {
     pub fn new(id: Id) -> Self {
         Self {
             id,
             settings: TreeViewSettings::default(),
+            context_menu: None,
+            filter: None,
         }
     }
 
@@ -233,11 +342,226 @@ impl TreeView {
         self
     }
 
+    /// Set whether or not nodes in this tree can be dragged.
+    ///
+    /// Use this to make the tree read-only with respect to drag and drop.
+    /// Individual nodes can also prevent dragging with [`node::NodeBuilder::drag_allowed`].
+    ///
+    /// Default is `true`.
+    pub fn allow_drag(mut self, allow_drag: bool) -> Self {
+        self.settings.allow_drag = allow_drag;
+        self
+    }
+
+    /// Set whether or not nodes in this tree can be renamed.
+    ///
+    /// If enabled, pressing F2 or clicking a selected node again starts
+    /// renaming the selected node. Pressing enter commits the new name
+    /// with [`Action::Rename`], pressing escape cancels the rename.
+    /// Only nodes with a text set by [`node::NodeBuilder::text`] can be renamed.
+    ///
+    /// Default is `false`.
+    pub fn allow_rename(mut self, allow_rename: bool) -> Self {
+        self.settings.allow_rename = allow_rename;
+        self
+    }
+
+    /// Add a context menu to the tree view.
+    ///
+    /// The context menu is shown when the empty space of the tree is secondary clicked
+    /// or when a node is secondary clicked that does not have its own context menu
+    /// set with [`node::NodeBuilder::context_menu`].
+    /// The closure receives the ids of the nodes the context menu was opened for.
+    /// For the empty space the list of ids is empty. If the clicked node is selected,
+    /// the list contains all selected nodes.
+    pub fn context_menu(
+        mut self,
+        add_context_menu: impl FnMut(&mut Ui, &[NodeIdType]) + 'tree,
+    ) -> Self {
+        self.context_menu = Some(Box::new(add_context_menu));
+        self
+    }
+
+    /// Set the search query of the tree.
+    ///
+    /// The parts of the labels added with [`TreeViewBuilder::leaf`] and
+    /// [`TreeViewBuilder::dir`] that match the query are highlighted.
+    /// An empty query highlights nothing.
+    pub fn search_query(mut self, query: impl Into<String>) -> Self {
+        let query = query.into();
+        self.settings.search_query = (!query.is_empty()).then_some(query);
+        self
+    }
+
+    /// Set how the search query is matched against the labels.
+    ///
+    /// Default is [`SearchMode::Exact`].
+    pub fn search_mode(mut self, mode: SearchMode) -> Self {
+        self.settings.search_mode = mode;
+        self
+    }
+
+    /// Set whether or not the search popup can be opened.
+    ///
+    /// If enabled, pressing Ctrl+F while the tree is focused opens a popup to
+    /// search the text of all nodes, including nodes in closed directories.
+    /// Picking a result expands its parents, selects it and scrolls to it.
+    /// Only nodes with a text set by [`node::NodeBuilder::text`] can be found.
+    ///
+    /// Default is `false`.
+    pub fn search_popup(mut self, search_popup: bool) -> Self {
+        self.settings.search_popup = search_popup;
+        self
+    }
+
+    /// Set whether or not the row under the pointer is highlighted.
+    ///
+    /// Default is `false`.
+    pub fn row_hover_highlight(mut self, row_hover_highlight: bool) -> Self {
+        self.settings.row_hover_highlight = row_hover_highlight;
+        self
+    }
+
+    /// Set whether or not every other row is painted with a background color.
+    ///
+    /// Default is `false`.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.settings.striped = striped;
+        self
+    }
+
+    /// Set the background color of striped rows.
+    ///
+    /// This is a shorthand for setting [`TreeViewStyle::stripe_bg_fill`]
+    /// and takes precedence over the style of the tree view.
+    pub fn stripe_color(mut self, color: Color32) -> Self {
+        self.settings.stripe_color = Some(color);
+        self
+    }
+
+    /// Set whether or not nodes without an icon show a built-in folder or file icon.
+    ///
+    /// The icons are only shown if the [`RowLayout`] shows icons.
+    /// Default is `false`.
+    pub fn default_icons(mut self, default_icons: bool) -> Self {
+        self.settings.default_icons = default_icons;
+        self
+    }
+
+    /// Set whether or not the vline of the directory that contains the selected
+    /// node is highlighted with [`TreeViewStyle::vline_active_stroke`].
+    ///
+    /// Default is `false`.
+    pub fn highlight_active_vline(mut self, highlight_active_vline: bool) -> Self {
+        self.settings.highlight_active_vline = highlight_active_vline;
+        self
+    }
+
+    /// Set the height of every row.
+    ///
+    /// The content of the rows is centered vertically. If no row height is set,
+    /// the height of a row depends on its content and the [`RowDensity`].
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.settings.row_height = Some(row_height);
+        self
+    }
+
+    /// Set the density of the rows.
+    ///
+    /// Compact and comfortable densities give every row the same height
+    /// relative to the interact size of the ui. Has no effect if a row height is set
+    /// with [`Self::row_height`].
+    /// Default is [`RowDensity::Standard`].
+    pub fn density(mut self, density: RowDensity) -> Self {
+        self.settings.density = density;
+        self
+    }
+
+    /// Set whether or not the tree is laid out from right to left.
+    ///
+    /// Use this for right-to-left languages. The indentation, closers, icons
+    /// and vlines start from the right side of the tree, the additional columns
+    /// are shown on the left side and the left and right arrow keys are swapped.
+    ///
+    /// Default is `false`.
+    pub fn right_to_left(mut self, right_to_left: bool) -> Self {
+        self.settings.right_to_left = right_to_left;
+        self
+    }
+
+    /// Set whether or not the ancestors of the top-most visible row are pinned
+    /// to the top of the viewport when the tree is inside a scroll area.
+    ///
+    /// The pinned rows show the text of the node set with
+    /// [`NodeBuilder::text`](node::NodeBuilder::text). Clicking a pinned row
+    /// selects the node and scrolls to it.
+    ///
+    /// Default is `false`.
+    pub fn sticky_headers(mut self, sticky_headers: bool) -> Self {
+        self.settings.sticky_headers = sticky_headers;
+        self
+    }
+
+    /// Set whether or not the children of a directory slide open and closed
+    /// when the directory is expanded or collapsed.
+    ///
+    /// Default is `false`.
+    pub fn animate_dirs(mut self, animate_dirs: bool) -> Self {
+        self.settings.animate_dirs = animate_dirs;
+        self
+    }
+
+    /// Set the style of the tree view.
+    ///
+    /// If no style is set, the style is created from the style of the ui with
+    /// [`TreeViewStyle::from_egui_style`].
+    pub fn style(mut self, style: TreeViewStyle) -> Self {
+        self.settings.style = Some(style);
+        self
+    }
+
+    /// Add a column to the right of the tree column.
+    ///
+    /// If the tree has columns, a header row is shown above the tree.
+    /// The tree column fills the space that is not used by the other columns.
+    pub fn column(mut self, column: Column) -> Self {
+        self.settings.columns.push(column);
+        self
+    }
+
+    /// Set the header of the tree column.
+    ///
+    /// The header is only shown if the tree has additional columns.
+    pub fn tree_column_header(mut self, header: impl Into<WidgetText>) -> Self {
+        self.settings.tree_column_header = header.into();
+        self
+    }
+
+    /// Set whether or not the tree can be sorted by the tree column.
+    ///
+    /// See [`Column::sortable`].
+    ///
+    /// Default is `false`.
+    pub fn tree_column_sortable(mut self, sortable: bool) -> Self {
+        self.settings.tree_column_sortable = sortable;
+        self
+    }
+
+    /// Filter the nodes of the tree.
+    ///
+    /// Only nodes that match the filter and directories that contain a matching
+    /// node are shown. Directories that contain a matching node are expanded while
+    /// the filter is set without changing their stored open state.
+    pub fn filter(mut self, filter: impl Fn(&NodeIdType) -> bool + 'tree) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
     /// Start displaying the tree view.
     ///
     /// Construct the tree view using the [`TreeViewBuilder`] by adding
     /// directories or leaves to the tree.
-    pub fn show<NodeIdType>(
+    pub fn show(
         self,
         ui: &mut Ui,
         build_tree_view: impl FnMut(TreeViewBuilder<'_, '_, NodeIdType>),
@@ -256,7 +580,7 @@ impl TreeView {
     ///
     /// Construct the tree view using the [`TreeViewBuilder`] by addind
     /// directories or leaves to the tree.
-    pub fn show_state<NodeIdType>(
+    pub fn show_state(
         mut self,
         ui: &mut Ui,
         state: &mut TreeViewState<NodeIdType>,
@@ -275,6 +599,18 @@ impl TreeView {
             self.settings.max_height = f32::INFINITY;
         }
 
+        // The density is a preset for the row height.
+        if self.settings.row_height.is_none() {
+            /// Difference between the row height and the interact size for a density.
+            const DENSITY_OFFSET: f32 = 4.0;
+            let interact_height = ui.spacing().interact_size.y;
+            self.settings.row_height = match self.settings.density {
+                RowDensity::Compact => Some(interact_height - DENSITY_OFFSET),
+                RowDensity::Standard => None,
+                RowDensity::Comfortable => Some(interact_height + DENSITY_OFFSET),
+            };
+        }
+
         // Set the focus filter to get correct keyboard navigation while focused.
         ui.memory_mut(|m| {
             m.set_focus_lock_filter(
@@ -289,9 +625,35 @@ impl TreeView {
         });
 
         // Create the tree state by loading the previous frame and setting up the state.
-        let mut data = TreeViewData::new(ui, state, self.id);
+        let mut style = self
+            .settings
+            .style
+            .unwrap_or_else(|| TreeViewStyle::from_egui_style(ui.style()));
+        if let Some(stripe_color) = self.settings.stripe_color {
+            style.stripe_bg_fill = stripe_color;
+        }
+        let mut data = TreeViewData::new(ui, state, self.id, style);
+        if let Some(filter) = self.filter.as_deref() {
+            data.set_filter(filter);
+        }
         let prev_selection = data.peristant.selected;
 
+        // Reset the column widths if the columns have changed.
+        if data.peristant.column_widths.len() != self.settings.columns.len() {
+            data.peristant.column_widths = self
+                .settings
+                .columns
+                .iter()
+                .map(|column| column.width)
+                .collect();
+        }
+
+        // A secondary click resets the secondary selection. If a node was clicked,
+        // it will set itself as the secondary selection while building the tree.
+        if data.interaction_response.secondary_clicked() {
+            data.peristant.secondary_selection = None;
+        }
+
         // Calculate the desired size of the tree view widget.
         let size = vec2(
             if self.settings.fill_space_horizontal {
@@ -312,6 +674,9 @@ impl TreeView {
         let used_rect = ui
             .allocate_ui_with_layout(size, Layout::top_down(egui::Align::Min), |ui| {
                 ui.set_min_size(vec2(self.settings.min_width, self.settings.min_height));
+                if !self.settings.columns.is_empty() {
+                    show_column_headers(ui, &mut data, &self.settings);
+                }
                 ui.add_space(ui.spacing().item_spacing.y * 0.5);
                 build_tree_view(TreeViewBuilder::new(ui, &mut data, &self.settings));
                 // Add negative space because the place will add the item spacing on top of this.
@@ -330,6 +695,78 @@ impl TreeView {
         // use new node states
         data.peristant.node_states = data.new_node_states.clone();
 
+        // The directories that contain a match were found using the node states
+        // of the previous frame. If the tree changed, a match can still be hidden
+        // so the tree is shown again with the new node states.
+        if let Some(filter) = data.filter {
+            if filter_ancestors(&data.new_node_states, filter) != data.filter_ancestors {
+                ui.ctx().request_repaint();
+            }
+        }
+
+        // The context menu opened from the keyboard is shown by the selected node.
+        // If that node was not shown this frame, drop the request so the menu
+        // does not pop up later at an unexpected moment.
+        data.peristant.open_context_menu = false;
+        // The same goes for a rename that did not start because its node was not shown.
+        if data
+            .peristant
+            .renaming
+            .as_ref()
+            .is_some_and(|rename_state| !rename_state.initialized)
+        {
+            data.peristant.renaming = None;
+        }
+
+        // Paint the row backgrounds below all rows.
+        let row_backgrounds = data
+            .row_backgrounds
+            .iter()
+            .map(|(row, color)| epaint::RectShape::filled(*row, Rounding::ZERO, *color).into())
+            .collect::<Vec<Shape>>();
+        ui.painter()
+            .set(data.row_backgrounds_idx, Shape::Vec(row_backgrounds));
+
+        show_bubbled_decorations(ui.painter(), &data);
+
+        if self.settings.sticky_headers {
+            show_sticky_headers(ui, &mut data, &self.settings);
+        }
+
+        // Show the context menu of the tree for the empty space or for nodes
+        // without their own context menu.
+        if let Some(context_menu) = self.context_menu.as_mut() {
+            let show_context_menu = data.peristant.secondary_selection.is_none()
+                || data.secondary_selection_row.is_some();
+            if show_context_menu {
+                let ids = match data.peristant.secondary_selection {
+                    Some(id) if data.is_selected(&id) => {
+                        data.peristant.selected.into_iter().collect::<Vec<_>>()
+                    }
+                    Some(id) => vec![id],
+                    None => Vec::new(),
+                };
+                let mut context_menu_visible = false;
+                data.interaction_response.context_menu(|ui| {
+                    context_menu(ui, &ids);
+                    context_menu_visible = true;
+                });
+                if let Some(row) = data.secondary_selection_row {
+                    if !data.is_selected(&ids[0]) && context_menu_visible {
+                        ui.painter().set(
+                            data.secondary_selection_idx,
+                            epaint::RectShape::new(
+                                row,
+                                data.style.rounding,
+                                Color32::TRANSPARENT,
+                                data.style.secondary_selection_stroke,
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
         // If the tree was clicked it should receive focus.
         let tree_view_interact = data.interact(&used_rect);
         if tree_view_interact.clicked || tree_view_interact.drag_started {
@@ -346,19 +783,114 @@ impl TreeView {
                     .dragged
                     .as_ref()
                     .map(|drag_state| drag_state.node_id)
-                    .or(data.peristant.node_states.first().map(|n| n.id));
+                    .or(data
+                        .peristant
+                        .node_states
+                        .iter()
+                        .find(|n| n.visible && n.enabled)
+                        .map(|n| n.id));
             }
+            let mut clipboard_text = None;
             ui.input(|i| {
                 for event in i.events.iter() {
                     match event {
-                        Event::Key { key, pressed, .. } if *pressed => {
-                            handle_input(data.peristant, key)
+                        Event::Key {
+                            key: Key::F2,
+                            pressed: true,
+                            ..
+                        } if self.settings.allow_rename => {
+                            if let Some(selected) = data.peristant.selected {
+                                data.peristant.start_rename(selected);
+                            }
+                        }
+                        Event::Key {
+                            key: Key::F,
+                            pressed: true,
+                            modifiers,
+                            ..
+                        } if modifiers.command && self.settings.search_popup => {
+                            data.peristant.search_popup = Some(SearchPopupState::default());
+                        }
+                        Event::Key {
+                            key: Key::F10,
+                            pressed: true,
+                            modifiers,
+                            ..
+                        } if modifiers.shift_only() && data.peristant.selected.is_some() => {
+                            // Open the context menu of the selected node from the keyboard.
+                            data.peristant.secondary_selection = data.peristant.selected;
+                            data.peristant.open_context_menu = true;
+                        }
+                        // The integrations turn the clipboard shortcuts into these
+                        // events instead of key events.
+                        Event::Cut | Event::Copy | Event::Paste(_) => {
+                            if let Some(action) = clipboard_action(data.peristant, event) {
+                                if let Action::Cut(ids) | Action::Copy(ids) = &action {
+                                    clipboard_text = Some(node_clipboard_text(data.peristant, ids));
+                                }
+                                data.actions.push(action);
+                            }
+                        }
+                        Event::Key {
+                            key,
+                            pressed: true,
+                            modifiers,
+                            ..
+                        } => {
+                            if let Some(action) = command_action(data.peristant, key, modifiers) {
+                                data.actions.push(action);
+                            } else {
+                                // The arrow keys follow the direction of the tree.
+                                let key = match key {
+                                    Key::ArrowLeft if self.settings.right_to_left => {
+                                        &Key::ArrowRight
+                                    }
+                                    Key::ArrowRight if self.settings.right_to_left => {
+                                        &Key::ArrowLeft
+                                    }
+                                    key => key,
+                                };
+                                handle_input(data.peristant, key, &data.filter_ancestors)
+                            }
                         }
                         _ => (),
                     }
                 }
             });
+            // egui only sends a paste event if the clipboard contains text, so cut and
+            // copied nodes put their text on the clipboard for pasting them again.
+            if let Some(text) = clipboard_text {
+                ui.ctx().copy_text(text);
+            }
         }
+        // Stop renaming if the text edit lost focus without the renamed node being shown.
+        if data
+            .peristant
+            .renaming
+            .as_ref()
+            .is_some_and(|rename_state| rename_state.initialized)
+        {
+            let edit_id = data.rename_edit_id();
+            if !ui.memory(|m| m.has_focus(edit_id) || m.had_focus_last_frame(edit_id)) {
+                data.peristant.renaming = None;
+            }
+        }
+
+        // A second click on a selected node starts a rename if it did not
+        // turn into a double click.
+        if let Some((node_id, click_time)) = data.peristant.rename_click {
+            let double_click_delay = ui.ctx().options(|o| o.input_options.max_double_click_delay);
+            let time_since_click = ui.input(|i| i.time) - click_time;
+            if data.peristant.selected != Some(node_id) {
+                data.peristant.rename_click = None;
+            } else if time_since_click > double_click_delay {
+                data.peristant.start_rename(node_id);
+            } else {
+                ui.ctx()
+                    .request_repaint_after_secs((double_click_delay - time_since_click) as f32);
+            }
+        }
+
         // Update the drag state
         // A drag only becomes a valid drag after the pointer has traveled some distance.
         if let Some(drag_state) = data.peristant.dragged.as_mut() {
@@ -390,6 +922,8 @@ impl TreeView {
                 }
             }
         }
+        show_search_popup(ui, data.peristant, self.id, used_rect);
+
         // Create a selection action.
         if data.peristant.selected != prev_selection {
             data.actions
@@ -412,7 +946,85 @@ impl TreeView {
     }
 }
 
-fn handle_input<NodeIdType: TreeViewId>(state: &mut TreeViewState<NodeIdType>, key: &Key) {
+/// Create the command action for a key press if the key is a command shortcut.
+fn command_action<NodeIdType: TreeViewId>(
+    state: &TreeViewState<NodeIdType>,
+    key: &Key,
+    modifiers: &Modifiers,
+) -> Option<Action<NodeIdType>> {
+    let selected = state.selected?;
+    let selected_ids = vec![selected];
+    match key {
+        Key::Delete => Some(Action::Delete(selected_ids)),
+        Key::D if modifiers.command => Some(Action::Duplicate(selected_ids)),
+        _ => None,
+    }
+}
+
+/// Create the action for a cut, copy or paste event.
+fn clipboard_action<NodeIdType: TreeViewId>(
+    state: &TreeViewState<NodeIdType>,
+    event: &Event,
+) -> Option<Action<NodeIdType>> {
+    let selected = state.selected?;
+    match event {
+        Event::Cut => Some(Action::Cut(vec![selected])),
+        Event::Copy => Some(Action::Copy(vec![selected])),
+        Event::Paste(_) => paste_action(state, selected),
+        _ => None,
+    }
+}
+
+/// Create the text that is placed on the clipboard for cut or copied nodes.
+///
+/// The text is never empty, otherwise the integrations do not send the paste event.
+/// Nodes without a text are replaced by a placeholder.
+fn node_clipboard_text<NodeIdType: TreeViewId>(
+    state: &TreeViewState<NodeIdType>,
+    ids: &[NodeIdType],
+) -> String {
+    ids.iter()
+        .map(|id| {
+            state
+                .node_state_of(id)
+                .and_then(|node_state| node_state.text.clone())
+                .filter(|text| !text.is_empty())
+                .unwrap_or_else(|| String::from("node"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Create the paste action for the selected node.
+///
+/// Nodes that allow dropping receive the pasted nodes as their last children.
+/// For all other nodes the pasted nodes are placed after the selected node.
+fn paste_action<NodeIdType: TreeViewId>(
+    state: &TreeViewState<NodeIdType>,
+    selected: NodeIdType,
+) -> Option<Action<NodeIdType>> {
+    let node_state = state.node_state_of(&selected)?;
+    if node_state.drop_allowed {
+        return Some(Action::Paste {
+            target: selected,
+            position: DropPosition::Last,
+        });
+    }
+    node_state.parent_id.map(|parent_id| Action::Paste {
+        target: parent_id,
+        position: DropPosition::After(selected),
+    })
+}
+
+/// Handle the navigation keys.
+///
+/// Directories in `filter_ancestors` are shown open because they contain a node
+/// that matches the filter. Their stored open state is not changed.
+fn handle_input<NodeIdType: TreeViewId>(
+    state: &mut TreeViewState<NodeIdType>,
+    key: &Key,
+    filter_ancestors: &HashSet<NodeIdType>,
+) {
     let Some(selected_id) = &state.selected else {
         return;
     };
@@ -424,49 +1036,76 @@ fn handle_input<NodeIdType: TreeViewId>(state: &mut TreeViewState<NodeIdType>, k
         return;
     };
     let node_state = &mut state.node_states[selected_index];
+    let filter_open = filter_ancestors.contains(&node_state.id);
+    let is_open = node_state.open || filter_open;
 
     match key {
         Key::ArrowUp => {
-            if selected_index > 0 {
-                if let Some(node) =
-                    // Search for previous visible node.
-                    state.node_states[0..selected_index]
-                        .iter()
-                        .rev()
-                        .find(|node| node.visible)
-                {
-                    state.selected = Some(node.id);
-                }
+            if let Some(node) =
+                // Search for previous visible node.
+                state.node_states[0..selected_index]
+                    .iter()
+                    .rev()
+                    .find(|node| node.visible && node.enabled)
+            {
+                state.selected = Some(node.id);
             }
         }
         Key::ArrowDown => {
-            if selected_index < state.node_states.len() - 1 {
-                // Search for previous visible node.
-                if let Some(node) = state.node_states[(selected_index + 1)..]
-                    .iter()
-                    .find(|node| node.visible)
-                {
-                    state.selected = Some(node.id);
-                }
+            // Search for next visible node.
+            if let Some(node) = state.node_states[(selected_index + 1)..]
+                .iter()
+                .find(|node| node.visible && node.enabled)
+            {
+                state.selected = Some(node.id);
             }
         }
         Key::ArrowLeft => {
-            if node_state.open {
+            if is_open && !filter_open {
                 node_state.open = false;
-            } else if node_state.parent_id.is_some() {
-                state.selected = node_state.parent_id;
+            } else {
+                // Directories expanded by the filter cannot be closed,
+                // so the parent is selected like for a closed directory.
+                // Select the closest ancestor that is enabled.
+                let mut current_parent = node_state.parent_id;
+                while let Some(parent) = current_parent
+                    .and_then(|parent_id| state.node_states.iter().find(|ns| ns.id == parent_id))
+                {
+                    if parent.enabled {
+                        state.selected = Some(parent.id);
+                        break;
+                    }
+                    current_parent = parent.parent_id;
+                }
             }
         }
         Key::ArrowRight => {
-            if node_state.open {
-                if selected_index < state.node_states.len() - 1 {
-                    // Search for previous visible node.
-                    if let Some(node) = state.node_states[(selected_index + 1)..]
-                        .iter()
-                        .find(|node| node.visible)
-                    {
-                        state.selected = Some(node.id);
+            if is_open {
+                let selected_id = node_state.id;
+                let parents = state
+                    .node_states
+                    .iter()
+                    .map(|node_state| (node_state.id, node_state.parent_id))
+                    .collect::<HashMap<_, _>>();
+                let is_descendant = |node: &NodeState<NodeIdType>| {
+                    let mut current_parent = node.parent_id;
+                    while let Some(parent_id) = current_parent {
+                        if parent_id == selected_id {
+                            return true;
+                        }
+                        current_parent = parents.get(&parent_id).copied().flatten();
                     }
+                    false
+                };
+                // Search for the first visible row inside the directory that is enabled.
+                // The children of flattened directories are not direct children
+                // of the selected node, so all descendants are searched.
+                if let Some(node) = state.node_states[(selected_index + 1)..]
+                    .iter()
+                    .take_while(|node| is_descendant(node))
+                    .find(|node| node.visible && node.enabled)
+                {
+                    state.selected = Some(node.id);
                 }
             } else {
                 node_state.open = true;
@@ -481,6 +1120,8 @@ fn handle_input<NodeIdType: TreeViewId>(state: &mut TreeViewState<NodeIdType>, k
 /// further structure because abstracting this more simply
 /// increases the complexity without much benefit.
 struct TreeViewData<'state, NodeIdType> {
+    /// Id of the tree view.
+    id: Id,
     /// State of the tree that is persistant across frames.
     peristant: &'state mut TreeViewState<NodeIdType>,
     /// Response of the interaction.
@@ -489,15 +1130,42 @@ struct TreeViewData<'state, NodeIdType> {
     drop: Option<(NodeIdType, DropPosition<NodeIdType>)>,
     /// Shape index of the drop marker
     drop_marker_idx: ShapeIdx,
+    /// Shape index of the secondary selection marker.
+    secondary_selection_idx: ShapeIdx,
+    /// Shape index of the row backgrounds.
+    row_backgrounds_idx: ShapeIdx,
+    /// Rows that are painted with a background color like the stripes.
+    row_backgrounds: Vec<(Rect, Color32)>,
+    /// Row of the secondary selected node if that node has no context menu of its own.
+    secondary_selection_row: Option<Rect>,
     /// Wether or not the tree view has keyboard focus.
     has_focus: bool,
     /// Actions for the tree view.
     actions: Vec<Action<NodeIdType>>,
     /// New node states for when this frame is done.
     new_node_states: Vec<NodeState<NodeIdType>>,
+    /// Style of the tree view.
+    style: TreeViewStyle,
+    /// Nodes with a decoration that bubbles up to their ancestors and the color of the decoration.
+    bubbling_decorations: Vec<(NodeIdType, Color32)>,
+    /// Position of the decoration dot of each shown directory.
+    decoration_dots: HashMap<NodeIdType, Pos2>,
+    /// Rows that were shown, for the sticky headers.
+    sticky_rows: Vec<StickyRow<NodeIdType>>,
+    /// Bottom of the last child of each directory, for the sticky headers.
+    sticky_bottoms: HashMap<NodeIdType, f32>,
+    /// Filter for the nodes of the tree.
+    filter: Option<&'state Filter<'state, NodeIdType>>,
+    /// Directories that contain a node that matches the filter.
+    filter_ancestors: HashSet<NodeIdType>,
 }
 impl<'state, NodeIdType> TreeViewData<'state, NodeIdType> {
-    fn new(ui: &mut Ui, state: &'state mut TreeViewState<NodeIdType>, id: Id) -> Self {
+    fn new(
+        ui: &mut Ui,
+        state: &'state mut TreeViewState<NodeIdType>,
+        id: Id,
+        style: TreeViewStyle,
+    ) -> Self {
         let interaction_response = interact_no_expansion(
             ui,
             Rect::from_min_size(ui.cursor().min, state.size),
@@ -507,15 +1175,63 @@ impl<'state, NodeIdType> TreeViewData<'state, NodeIdType> {
         let has_focus = ui.memory(|m| m.has_focus(id));
 
         TreeViewData {
+            id,
             peristant: state,
             drop: None,
+            // The row backgrounds are painted first so they are below everything else.
+            row_backgrounds_idx: ui.painter().add(Shape::Noop),
+            row_backgrounds: Vec::new(),
             drop_marker_idx: ui.painter().add(Shape::Noop),
+            secondary_selection_idx: ui.painter().add(Shape::Noop),
+            secondary_selection_row: None,
             interaction_response,
             has_focus,
             actions: Vec::new(),
             new_node_states: Vec::new(),
+            style,
+            bubbling_decorations: Vec::new(),
+            decoration_dots: HashMap::new(),
+            sticky_rows: Vec::new(),
+            sticky_bottoms: HashMap::new(),
+            filter: None,
+            filter_ancestors: HashSet::new(),
+        }
+    }
+}
+impl<'state, NodeIdType: TreeViewId> TreeViewData<'state, NodeIdType> {
+    /// Set the filter and find all directories that contain a matching node.
+    ///
+    /// The directories are found using the node states of the previous frame.
+    fn set_filter(&mut self, filter: &'state Filter<'state, NodeIdType>) {
+        self.filter_ancestors = filter_ancestors(&self.peristant.node_states, filter);
+        self.filter = Some(filter);
+    }
+}
+
+/// Find all directories that contain a node that matches the filter.
+fn filter_ancestors<NodeIdType: TreeViewId>(
+    node_states: &[NodeState<NodeIdType>],
+    filter: &Filter<NodeIdType>,
+) -> HashSet<NodeIdType> {
+    let parents = node_states
+        .iter()
+        .map(|node_state| (node_state.id, node_state.parent_id))
+        .collect::<HashMap<_, _>>();
+
+    let mut ancestors = HashSet::new();
+    for node_state in node_states.iter() {
+        if !filter(&node_state.id) {
+            continue;
+        }
+        let mut current_parent = node_state.parent_id;
+        while let Some(parent_id) = current_parent {
+            if !ancestors.insert(parent_id) {
+                break;
+            }
+            current_parent = parents.get(&parent_id).copied().flatten();
         }
     }
+    ancestors
 }
 impl<NodeIdType: TreeViewId> TreeViewData<'_, NodeIdType> {
     pub fn interact(&self, rect: &Rect) -> Interaction {
@@ -524,13 +1240,7 @@ impl<NodeIdType: TreeViewId> TreeViewData<'_, NodeIdType> {
             .hover_pos()
             .is_some_and(|pos| rect.contains(pos))
         {
-            return Interaction {
-                clicked: false,
-                double_clicked: false,
-                secondary_clicked: false,
-                hovered: false,
-                drag_started: false,
-            };
+            return Interaction::default();
         }
 
         Interaction {
@@ -563,6 +1273,56 @@ impl<NodeIdType: TreeViewId> TreeViewData<'_, NodeIdType> {
         self.peristant.selected.as_ref().is_some_and(|n| n == id)
     }
 
+    pub fn is_renaming(&self, id: &NodeIdType) -> bool {
+        self.peristant
+            .renaming
+            .as_ref()
+            .is_some_and(|rename_state| &rename_state.node_id == id)
+    }
+
+    /// Id of the text edit used to rename a node.
+    pub fn rename_edit_id(&self) -> Id {
+        self.id.with("rename text edit")
+    }
+
+    /// Is the node shown with the current filter.
+    pub fn passes_filter(&self, id: &NodeIdType) -> bool {
+        self.filter
+            .is_none_or(|filter| filter(id) || self.filter_ancestors.contains(id))
+    }
+
+    /// Does the directory contain a node that matches the current filter.
+    pub fn contains_filter_match(&self, id: &NodeIdType) -> bool {
+        self.filter_ancestors.contains(id)
+    }
+
+    /// The horizontal ranges of the tree column and the additional columns
+    /// for a row with the given horizontal range.
+    ///
+    /// If `right_to_left` is set, the columns are placed on the left side of the row.
+    pub fn column_ranges(&self, row: Rangef, right_to_left: bool) -> (Rangef, Vec<Rangef>) {
+        let mut column_ranges = Vec::with_capacity(self.peristant.column_widths.len());
+        let mut right = row.max;
+        for width in self.peristant.column_widths.iter().rev() {
+            let left = (right - width).at_least(row.min);
+            column_ranges.push(Rangef::new(left, right));
+            right = left;
+        }
+        column_ranges.reverse();
+        let tree_column = Rangef::new(row.min, right);
+        if right_to_left {
+            let mirror = |range: Rangef| {
+                Rangef::new(row.min + row.max - range.max, row.min + row.max - range.min)
+            };
+            (
+                mirror(tree_column),
+                column_ranges.into_iter().map(mirror).collect(),
+            )
+        } else {
+            (tree_column, column_ranges)
+        }
+    }
+
     pub fn is_secondary_selected(&self, id: &NodeIdType) -> bool {
         self.peristant
             .secondary_selection
@@ -571,6 +1331,7 @@ impl<NodeIdType: TreeViewId> TreeViewData<'_, NodeIdType> {
     }
 }
 
+#[derive(Default)]
 struct Interaction {
     pub clicked: bool,
     pub double_clicked: bool,
@@ -614,6 +1375,25 @@ struct TreeViewSettings {
     min_height: f32,
     fill_space_horizontal: bool,
     fill_space_vertical: bool,
+    allow_drag: bool,
+    allow_rename: bool,
+    search_query: Option<String>,
+    search_mode: SearchMode,
+    search_popup: bool,
+    columns: Vec<Column>,
+    tree_column_header: WidgetText,
+    tree_column_sortable: bool,
+    row_hover_highlight: bool,
+    striped: bool,
+    stripe_color: Option<Color32>,
+    animate_dirs: bool,
+    sticky_headers: bool,
+    highlight_active_vline: bool,
+    default_icons: bool,
+    row_height: Option<f32>,
+    density: RowDensity,
+    right_to_left: bool,
+    style: Option<TreeViewStyle>,
 }
 
 impl Default for TreeViewSettings {
@@ -628,6 +1408,25 @@ impl Default for TreeViewSettings {
             min_height: 0.0,
             fill_space_horizontal: true,
             fill_space_vertical: false,
+            allow_drag: true,
+            allow_rename: false,
+            search_query: None,
+            search_mode: Default::default(),
+            search_popup: false,
+            columns: Vec::new(),
+            tree_column_header: WidgetText::default(),
+            tree_column_sortable: false,
+            row_hover_highlight: false,
+            striped: false,
+            stripe_color: None,
+            animate_dirs: false,
+            sticky_headers: false,
+            highlight_active_vline: false,
+            default_icons: false,
+            row_height: None,
+            density: Default::default(),
+            right_to_left: false,
+            style: None,
         }
     }
 }
@@ -642,6 +1441,24 @@ pub enum VLineStyle {
     /// A vline is show with horizontal hooks to the child nodes of the directory.
     #[default]
     Hook,
+    /// A vline is shown for the full hight of the directory with horizontal hooks
+    /// that end at the closer of each child node.
+    FullHook,
+    /// A dashed vertical line is shown for the full hight of the directory.
+    Dashed,
+    /// A dotted vertical line is shown for the full hight of the directory.
+    Dotted,
+}
+
+/// How a search query is matched against the text of a node.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// The text must contain the query.
+    #[default]
+    Exact,
+    /// The text must contain all characters of the query in order
+    /// but not necessarily next to each other.
+    Fuzzy,
 }
 
 /// How rows in the tree are layed out.
@@ -670,6 +1487,18 @@ pub enum RowLayout {
     AlignedIconsAndLabels,
 }
 
+/// How much space the rows of the tree view take up.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum RowDensity {
+    /// Rows are smaller than the interact size of the ui.
+    Compact,
+    /// Rows are as high as their content.
+    #[default]
+    Standard,
+    /// Rows are higher than the interact size of the ui.
+    Comfortable,
+}
+
 /// An action the tree view would like to take as a result
 /// of some user input like drag and drop.
 #[derive(Clone)]
@@ -689,6 +1518,36 @@ pub enum Action<NodeIdType> {
         target: NodeIdType,
         position: DropPosition<NodeIdType>,
     },
+    /// A node was renamed.
+    Rename { id: NodeIdType, new_name: String },
+    /// The nodes should be deleted.
+    Delete(Vec<NodeIdType>),
+    /// The nodes should be cut to the clipboard.
+    ///
+    /// The tree view puts the text of the nodes on the system clipboard.
+    Cut(Vec<NodeIdType>),
+    /// The nodes should be copied to the clipboard.
+    ///
+    /// The tree view puts the text of the nodes on the system clipboard.
+    Copy(Vec<NodeIdType>),
+    /// The content of the clipboard should be pasted.
+    ///
+    /// This is emitted for the paste event of egui. Integrations like egui-winit
+    /// only send this event if the system clipboard contains text. Cutting or
+    /// copying nodes puts their text on the clipboard so pasting them always works.
+    Paste {
+        target: NodeIdType,
+        position: DropPosition<NodeIdType>,
+    },
+    /// The nodes should be duplicated.
+    Duplicate(Vec<NodeIdType>),
+    /// The tree should be sorted by a column.
+    /// The nodes should be sorted within their directory.
+    /// If the direction is `None`, the tree should not be sorted.
+    SortBy {
+        column: SortColumn,
+        direction: Option<SortDirection>,
+    },
 }
 
 pub struct TreeViewResponse<NodeIdType> {
@@ -710,6 +1569,26 @@ impl<NodeIdType: TreeViewId> TreeViewResponse<NodeIdType> {
     }
 }
 
+/// Open the context menu of a response at a position.
+///
+/// egui only opens context menus on a secondary click. To open it from the keyboard
+/// we have to create the menu root ourselfs.
+///
+/// This mirrors what `Response::context_menu` does internally in egui 0.30.
+/// The id is a copy of the `pub(crate)` constant `CONTEXT_MENU_ID_STR` in
+/// `egui/src/menu.rs`. Check both when upgrading egui.
+fn open_context_menu(response: &Response, pos: Pos2) {
+    // This is the id egui uses to store the state of context menus.
+    let menu_id = Id::new("__egui::context_menu");
+    let pos = response
+        .ctx
+        .layer_transform_to_global(response.layer_id)
+        .map_or(pos, |to_global| to_global * pos);
+    let mut bar_state = BarState::load(&response.ctx, menu_id);
+    MenuRoot::handle_menu_response(&mut bar_state, MenuResponse::Create(pos, response.id));
+    bar_state.store(&response.ctx, menu_id);
+}
+
 /// Interact with the ui without egui adding any extra space.
 fn interact_no_expansion(ui: &mut Ui, rect: Rect, id: Id, sense: Sense) -> Response {
     let spacing_before = ui.spacing().clone();
@@ -718,3 +1597,116 @@ fn interact_no_expansion(ui: &mut Ui, rect: Rect, id: Id, sense: Sense) -> Respo
     *ui.spacing_mut() = spacing_before;
     res
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use egui::Key;
+
+    use crate::{handle_input, node_clipboard_text, NodeState, TreeViewState};
+
+    fn node_state(id: i32, parent_id: Option<i32>, open: bool, visible: bool) -> NodeState<i32> {
+        NodeState {
+            id,
+            parent_id,
+            open,
+            visible,
+            drop_allowed: true,
+            enabled: true,
+            text: None,
+        }
+    }
+
+    fn tree_state(node_states: Vec<NodeState<i32>>, selected: i32) -> TreeViewState<i32> {
+        TreeViewState {
+            node_states,
+            selected: Some(selected),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn arrow_right_selects_the_first_child_of_a_flattened_dir() {
+        // 0
+        // └ 1 (flattened)
+        //   ├ 2
+        //   └ 3
+        // 4
+        let mut state = tree_state(
+            vec![
+                node_state(0, None, true, true),
+                node_state(1, Some(0), true, false),
+                node_state(2, Some(1), false, true),
+                node_state(3, Some(1), false, true),
+                node_state(4, None, false, true),
+            ],
+            0,
+        );
+
+        handle_input(&mut state, &Key::ArrowRight, &HashSet::new());
+
+        assert_eq!(state.selected, Some(2));
+    }
+
+    #[test]
+    fn arrow_right_skips_disabled_children_but_stays_in_the_dir() {
+        let mut state = tree_state(
+            vec![
+                node_state(0, None, true, true),
+                NodeState {
+                    enabled: false,
+                    ..node_state(1, Some(0), false, true)
+                },
+                node_state(2, None, false, true),
+            ],
+            0,
+        );
+
+        handle_input(&mut state, &Key::ArrowRight, &HashSet::new());
+
+        assert_eq!(state.selected, Some(0));
+    }
+
+    #[test]
+    fn arrow_keys_keep_the_open_state_of_dirs_expanded_by_the_filter() {
+        let mut state = tree_state(
+            vec![
+                node_state(0, None, false, true),
+                node_state(1, Some(0), false, true),
+            ],
+            0,
+        );
+        let filter_ancestors = HashSet::from([0]);
+
+        handle_input(&mut state, &Key::ArrowLeft, &filter_ancestors);
+        assert!(!state.node_states[0].open);
+        assert_eq!(state.selected, Some(0));
+
+        handle_input(&mut state, &Key::ArrowRight, &filter_ancestors);
+        assert!(!state.node_states[0].open);
+        assert_eq!(state.selected, Some(1));
+    }
+
+    #[test]
+    fn clipboard_text_is_never_empty() {
+        let state = tree_state(
+            vec![
+                NodeState {
+                    text: Some(String::from("foo")),
+                    ..node_state(0, None, false, true)
+                },
+                NodeState {
+                    text: Some(String::new()),
+                    ..node_state(1, None, false, true)
+                },
+                node_state(2, None, false, true),
+            ],
+            0,
+        );
+
+        assert_eq!(node_clipboard_text(&state, &[0]), "foo");
+        assert_eq!(node_clipboard_text(&state, &[1]), "node");
+        assert_eq!(node_clipboard_text(&state, &[0, 2]), "foo\nnode");
+    }
+}
@@ -1,9 +1,13 @@
 use egui::{
-    emath, epaint, remap, vec2, CursorIcon, Id, InnerResponse, LayerId, Order, Rangef, Rect,
-    Response, Shape, Stroke, Ui, UiBuilder, Vec2,
+    emath, epaint, pos2, remap, vec2, Align, Color32, CursorIcon, Id, InnerResponse, Label,
+    LayerId, Layout, Order, Rangef, Rect, Response, RichText, Shape, Stroke, TextEdit, Ui,
+    UiBuilder, Vec2, WidgetText,
 };
 
-use crate::{Interaction, RowLayout, TreeViewData, TreeViewId, TreeViewSettings};
+use crate::{
+    builder::add_label, Decoration, Interaction, RowLayout, TreeViewData, TreeViewId,
+    TreeViewSettings, TreeViewStyle,
+};
 
 pub type AddUi<'add_ui> = dyn FnMut(&mut Ui) + 'add_ui;
 pub type AddCloser<'add_ui> = dyn FnMut(&mut Ui, CloserState) + 'add_ui;
@@ -15,11 +19,23 @@ pub struct NodeBuilder<'add_ui, NodeIdType> {
     pub(crate) is_open: bool,
     pub(crate) default_open: bool,
     pub(crate) drop_allowed: bool,
+    pub(crate) drag_allowed: bool,
+    pub(crate) enabled: bool,
+    pub(crate) text: Option<String>,
+    pub(crate) text_color: Option<Color32>,
+    pub(crate) background_color: Option<Color32>,
+    italics: bool,
+    strong: bool,
+    pub(crate) decoration: Option<Decoration>,
     indent: usize,
     icon: Option<Box<AddUi<'add_ui>>>,
     closer: Option<Box<AddCloser<'add_ui>>>,
     label: Option<Box<AddUi<'add_ui>>>,
     context_menu: Option<Box<AddUi<'add_ui>>>,
+    columns: Vec<Box<AddUi<'add_ui>>>,
+    trailing: Option<Box<AddUi<'add_ui>>>,
+    tooltip: Option<Box<AddUi<'add_ui>>>,
+    style: Option<TreeViewStyle>,
 }
 impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
     /// Create a new node builder from a leaf prototype.
@@ -29,10 +45,22 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
             is_dir: false,
             flatten: false,
             drop_allowed: false,
+            drag_allowed: true,
+            enabled: true,
+            text: None,
+            text_color: None,
+            background_color: None,
+            italics: false,
+            strong: false,
+            decoration: None,
             icon: None,
             closer: None,
             label: None,
             context_menu: None,
+            columns: Vec::new(),
+            trailing: None,
+            tooltip: None,
+            style: None,
             is_open: false,
             default_open: true,
             indent: 0,
@@ -46,10 +74,22 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
             is_dir: true,
             flatten: false,
             drop_allowed: true,
+            drag_allowed: true,
+            enabled: true,
+            text: None,
+            text_color: None,
+            background_color: None,
+            italics: false,
+            strong: false,
+            decoration: None,
             icon: None,
             closer: None,
             label: None,
             context_menu: None,
+            columns: Vec::new(),
+            trailing: None,
+            tooltip: None,
+            style: None,
             is_open: false,
             default_open: true,
             indent: 0,
@@ -77,6 +117,72 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
         self
     }
 
+    /// Whether or not this node can be dragged.
+    ///
+    /// If `false`, the node cannot be used as the source of a drag and drop.
+    pub fn drag_allowed(mut self, drag_allowed: bool) -> Self {
+        self.drag_allowed = drag_allowed;
+        self
+    }
+
+    /// Whether or not this node is enabled.
+    ///
+    /// A disabled node is shown grayed out. It cannot be selected, dragged or
+    /// dropped into and it is skipped by the keyboard navigation.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the text of this node.
+    ///
+    /// The text is used to fill the text edit when the node is renamed.
+    /// If the node has no label, the text is shown as the label.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the color of the text of this node.
+    ///
+    /// The color is used for the fg stroke of the row so that labels added by
+    /// [`Self::label`] use it as well. While the node is selected and the tree view
+    /// has focus, the selection color is used instead to keep the text legible.
+    pub fn text_color(mut self, color: Color32) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Set a background color for the full row of this node.
+    ///
+    /// The background is painted below the selection.
+    pub fn background_color(mut self, color: Color32) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Whether or not the text of this node is shown in italics.
+    ///
+    /// Only applies to the label that shows the text set with [`Self::text`].
+    pub fn italics(mut self, italics: bool) -> Self {
+        self.italics = italics;
+        self
+    }
+
+    /// Whether or not the text of this node is shown with a strong color.
+    ///
+    /// Only applies to the label that shows the text set with [`Self::text`].
+    pub fn strong(mut self, strong: bool) -> Self {
+        self.strong = strong;
+        self
+    }
+
+    /// Add a status decoration to this node.
+    pub fn decoration(mut self, decoration: Decoration) -> Self {
+        self.decoration = Some(decoration);
+        self
+    }
+
     /// Add a icon to the node.
     pub fn icon(
         mut self,
@@ -105,6 +211,52 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
         self
     }
 
+    /// Add the content of the next column to this node.
+    ///
+    /// Columns are filled in the order they were added to the tree view
+    /// with [`TreeView::column`](crate::TreeView::column).
+    pub fn column(
+        mut self,
+        add_column: impl FnMut(&mut Ui) + 'add_ui,
+    ) -> NodeBuilder<'add_ui, NodeIdType> {
+        self.columns.push(Box::new(add_column));
+        self
+    }
+
+    /// Add widgets to the right side of the row.
+    ///
+    /// The widgets are layed out from right to left starting at the right edge of the
+    /// row, regardless of the indentation of the node. Clicking the widgets does not
+    /// select the node or start a drag.
+    pub fn trailing(
+        mut self,
+        add_trailing: impl FnMut(&mut Ui) + 'add_ui,
+    ) -> NodeBuilder<'add_ui, NodeIdType> {
+        self.trailing = Some(Box::new(add_trailing));
+        self
+    }
+
+    /// Add a tooltip to this node.
+    ///
+    /// The tooltip is shown when the pointer rests anywhere on the row.
+    pub fn tooltip(
+        mut self,
+        add_tooltip: impl FnMut(&mut Ui) + 'add_ui,
+    ) -> NodeBuilder<'add_ui, NodeIdType> {
+        self.tooltip = Some(Box::new(add_tooltip));
+        self
+    }
+
+    /// Add a tooltip with a text to this node.
+    ///
+    /// See [`Self::tooltip`].
+    pub fn tooltip_text(self, text: impl Into<WidgetText>) -> NodeBuilder<'add_ui, NodeIdType> {
+        let text = text.into();
+        self.tooltip(move |ui| {
+            ui.label(text.clone());
+        })
+    }
+
     /// Add a context menu to this node.
     pub fn context_menu(
         mut self,
@@ -114,6 +266,20 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
         self
     }
 
+    /// Override the style of the tree view for this node.
+    ///
+    /// Use [`TreeViewBuilder::style`](crate::TreeViewBuilder::style) to get the style
+    /// of the tree view and change only the parts that should look different.
+    pub fn style(mut self, style: TreeViewStyle) -> NodeBuilder<'add_ui, NodeIdType> {
+        self.style = Some(style);
+        self
+    }
+
+    /// The style of this node or the style of the tree view if it was not overridden.
+    pub(crate) fn effective_style(&self, state: &TreeViewData<NodeIdType>) -> TreeViewStyle {
+        self.style.unwrap_or(state.style)
+    }
+
     pub(crate) fn set_is_open(&mut self, open: bool) {
         self.is_open = open;
     }
@@ -127,32 +293,45 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
         ui: &mut Ui,
         state: &TreeViewData<NodeIdType>,
         settings: &TreeViewSettings,
-    ) -> (Rect, Option<Rect>, Option<Rect>, Rect) {
+    ) -> (Rect, Option<Rect>, Option<Rect>, Rect, Option<Rect>) {
+        let has_icon = self.icon.is_some() || settings.default_icons;
         let (reserve_closer, draw_closer, reserve_icon, draw_icon) = match settings.row_layout {
             RowLayout::Compact => (self.is_dir, self.is_dir, false, false),
             RowLayout::CompactAlignedLables => (
                 self.is_dir,
                 self.is_dir,
                 !self.is_dir,
-                !self.is_dir && self.icon.is_some(),
+                !self.is_dir && has_icon,
             ),
-            RowLayout::AlignedIcons => {
-                (true, self.is_dir, self.icon.is_some(), self.icon.is_some())
-            }
-            RowLayout::AlignedIconsAndLabels => (true, self.is_dir, true, self.icon.is_some()),
+            RowLayout::AlignedIcons => (true, self.is_dir, has_icon, has_icon),
+            RowLayout::AlignedIconsAndLabels => (true, self.is_dir, true, has_icon),
+        };
+
+        let style = self.effective_style(state);
+        let row_x_range = ui.available_rect_before_wrap().x_range();
+        let (tree_column, column_ranges) = state.column_ranges(row_x_range, settings.right_to_left);
+        let layout = if settings.right_to_left {
+            Layout::right_to_left(Align::Center)
+        } else {
+            Layout::left_to_right(Align::Center)
         };
 
-        let InnerResponse {
-            inner: (closer, icon, label),
-            response: row_response,
-        } = ui.horizontal(|ui| {
+        let mut add_row = |ui: &mut Ui| {
+            // Keep the content of the row inside the tree column.
+            if !settings.columns.is_empty() {
+                let clip_rect = ui
+                    .clip_rect()
+                    .intersect(Rect::from_x_y_ranges(tree_column, Rangef::EVERYTHING));
+                ui.set_clip_rect(clip_rect);
+            }
+
             // The layouting in the row has to be pretty tight so we tunr of the item spacing here.
             let original_item_spacing = ui.spacing().item_spacing;
             ui.spacing_mut().item_spacing = Vec2::ZERO;
 
             ui.add_space(original_item_spacing.x);
 
-            // Add a little space so the closer/icon/label doesnt touch the left side
+            // Add a little space so the closer/icon/label doesnt touch the side
             // and add the indentation space.
             ui.add_space(ui.spacing().item_spacing.x);
             ui.add_space(
@@ -161,9 +340,7 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
 
             // Draw the closer
             let closer = draw_closer.then(|| {
-                let (small_rect, big_rect) = ui
-                    .spacing()
-                    .icon_rectangles(ui.available_rect_before_wrap());
+                let (small_rect, big_rect) = icon_rectangles(ui, settings.right_to_left);
 
                 let res = ui.allocate_new_ui(UiBuilder::new().max_rect(big_rect), |ui| {
                     let closer_interaction = state.interact(&ui.max_rect());
@@ -182,7 +359,14 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
                         let icon_id = Id::new(self.id).with("tree view closer icon");
                         let openness = ui.ctx().animate_bool(icon_id, self.is_open);
                         let closer_interaction = state.interact(&ui.max_rect());
-                        paint_default_icon(ui, openness, &small_rect, &closer_interaction);
+                        paint_default_icon(
+                            ui,
+                            openness,
+                            &small_rect,
+                            &closer_interaction,
+                            &style,
+                            settings.right_to_left,
+                        );
                     }
                     ui.allocate_space(ui.available_size_before_wrap());
                 });
@@ -193,32 +377,52 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
             }
 
             // Draw icon
-            let icon = draw_icon
-                .then(|| {
-                    self.icon.as_mut().map(|add_icon| {
-                        let (_, big_rect) = ui
-                            .spacing()
-                            .icon_rectangles(ui.available_rect_before_wrap());
-                        ui.allocate_new_ui(UiBuilder::new().max_rect(big_rect), |ui| {
-                            ui.set_min_size(big_rect.size());
-                            add_icon(ui);
-                        })
-                        .response
-                        .rect
-                    })
+            let icon = draw_icon.then(|| {
+                let (_, big_rect) = icon_rectangles(ui, settings.right_to_left);
+                ui.allocate_new_ui(UiBuilder::new().max_rect(big_rect), |ui| {
+                    ui.set_min_size(big_rect.size());
+                    if let Some(add_icon) = self.icon.as_mut() {
+                        add_icon(ui);
+                    } else if self.is_dir {
+                        paint_folder_icon(ui, &big_rect, self.is_open, &style);
+                    } else {
+                        paint_file_icon(ui, &big_rect, &style);
+                    }
                 })
-                .flatten();
+                .response
+                .rect
+            });
             if icon.is_none() && reserve_icon {
                 ui.add_space(ui.spacing().icon_width);
             }
 
-            ui.add_space(2.0);
+            ui.add_space(style.label_gap);
             // Draw label
             let label = ui
                 .scope(|ui| {
                     ui.spacing_mut().item_spacing = original_item_spacing;
-                    if let Some(add_label) = self.label.as_mut() {
+                    if state.is_renaming(&self.id) {
+                        let edit_id = state.rename_edit_id();
+                        let mut text = ui
+                            .data_mut(|d| d.get_temp::<String>(edit_id))
+                            .unwrap_or_default();
+                        ui.add(
+                            TextEdit::singleline(&mut text)
+                                .id(edit_id)
+                                .desired_width(ui.available_width() - original_item_spacing.x),
+                        );
+                        ui.data_mut(|d| d.insert_temp(edit_id, text));
+                    } else if let Some(add_label) = self.label.as_mut() {
                         add_label(ui);
+                    } else if let Some(text) = self.text.as_ref() {
+                        let mut text = RichText::new(text);
+                        if self.italics {
+                            text = text.italics();
+                        }
+                        if self.strong {
+                            text = text.strong();
+                        }
+                        add_label(ui, &text.into(), settings, &style);
                     }
                 })
                 .response
@@ -227,14 +431,91 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
             ui.add_space(original_item_spacing.x);
 
             (closer, icon, label)
+        };
+        let ((closer, icon, label), row_rect) = match settings.row_height {
+            Some(row_height) => {
+                // Allocate the exact height for the row and center the content vertically.
+                let (_, rect) = ui.allocate_space(vec2(ui.available_width(), row_height));
+                let mut row_ui = ui.new_child(UiBuilder::new().max_rect(rect).layout(layout));
+                (add_row(&mut row_ui), rect)
+            }
+            None => {
+                let size = vec2(ui.available_width(), ui.spacing().interact_size.y);
+                let InnerResponse { inner, response } =
+                    ui.allocate_ui_with_layout(size, layout, add_row);
+                (inner, response.rect)
+            }
+        };
+
+        let row = Rect::from_x_y_ranges(
+            row_x_range,
+            row_rect
+                .expand2(vec2(0.0, ui.spacing().item_spacing.y * 0.5))
+                .y_range(),
+        );
+
+        // The badge and the trailing widgets are shown on the opposite side of the label.
+        let (mut trailing_range, trailing_layout) = if settings.right_to_left {
+            (
+                Rangef::new(
+                    tree_column.min + ui.spacing().item_spacing.x,
+                    tree_column.max,
+                ),
+                Layout::left_to_right(Align::Center),
+            )
+        } else {
+            (
+                Rangef::new(
+                    tree_column.min,
+                    tree_column.max - ui.spacing().item_spacing.x,
+                ),
+                Layout::right_to_left(Align::Center),
+            )
+        };
+
+        // Draw the badge of the decoration at the end of the tree column
+        // so that the badges of all rows line up.
+        let badge = self
+            .decoration
+            .as_ref()
+            .and_then(|decoration| Some((decoration.badge.as_ref()?, decoration.color)));
+        if let Some((badge, color)) = badge.filter(|_| !state.is_renaming(&self.id)) {
+            let mut badge_ui = ui.new_child(
+                UiBuilder::new()
+                    .max_rect(Rect::from_x_y_ranges(trailing_range, row.y_range()))
+                    .layout(trailing_layout),
+            );
+            badge_ui.add(Label::new(RichText::new(badge).small().color(color)).selectable(false));
+            let badge_rect = badge_ui.min_rect();
+            if settings.right_to_left {
+                trailing_range.min = badge_rect.right() + ui.spacing().item_spacing.x;
+            } else {
+                trailing_range.max = badge_rect.left() - ui.spacing().item_spacing.x;
+            }
+        }
+
+        // Draw the trailing widgets
+        let trailing = self.trailing.as_mut().map(|add_trailing| {
+            let trailing_rect = Rect::from_x_y_ranges(trailing_range, row.y_range());
+            let mut trailing_ui = ui.new_child(
+                UiBuilder::new()
+                    .max_rect(trailing_rect)
+                    .layout(trailing_layout),
+            );
+            add_trailing(&mut trailing_ui);
+            trailing_ui.min_rect()
         });
 
-        let mut row = row_response
-            .rect
-            .expand2(vec2(0.0, ui.spacing().item_spacing.y * 0.5));
-        row.set_width(ui.available_width());
+        // Draw the additional columns
+        for (add_column, range) in self.columns.iter_mut().zip(column_ranges) {
+            let column_rect = Rect::from_x_y_ranges(range, row.y_range())
+                .shrink2(vec2(ui.spacing().item_spacing.x, 0.0));
+            let mut column_ui = ui.new_child(UiBuilder::new().max_rect(column_rect).layout(layout));
+            column_ui.set_clip_rect(ui.clip_rect().intersect(column_rect));
+            add_column(&mut column_ui);
+        }
 
-        (row, closer, icon, label)
+        (row, closer, icon, label, trailing)
     }
 
     /// Draw the content as a drag overlay if it is beeing dragged.
@@ -260,14 +541,15 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
             .scope_builder(UiBuilder::new().layer_id(layer_id), |ui| {
                 let background_position = ui.painter().add(Shape::Noop);
 
-                let (row, _, _, _) = self.show_node(ui, state, settings);
+                let (row, _, _, _, _) = self.show_node(ui, state, settings);
 
+                let style = self.effective_style(state);
                 ui.painter().set(
                     background_position,
                     epaint::RectShape::new(
                         row,
-                        ui.visuals().widgets.active.rounding,
-                        ui.visuals().selection.bg_fill.linear_multiply(0.4),
+                        style.rounding,
+                        style.drag_overlay_fill,
                         Stroke::NONE,
                     ),
                 );
@@ -277,8 +559,13 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
 
         // Move layer to the drag position
         if let Some(pointer_pos) = ui.ctx().pointer_interact_pos() {
-            //let delta = -background_rect.min.to_vec2() + pointer_pos.to_vec2() + drag_offset;
-            let delta = -background_rect.min.to_vec2()
+            // The offset is relative to the side of the row the tree starts from.
+            let origin = if settings.right_to_left {
+                background_rect.right_top()
+            } else {
+                background_rect.left_top()
+            };
+            let delta = -origin.to_vec2()
                 + pointer_pos.to_vec2()
                 + state.peristant.dragged.as_ref().unwrap().drag_row_offset;
             if delta != Vec2::ZERO {
@@ -290,6 +577,16 @@ impl<'add_ui, NodeIdType: TreeViewId> NodeBuilder<'add_ui, NodeIdType> {
         true
     }
 
+    pub(crate) fn show_tooltip(&mut self, response: &Response) {
+        if let Some(tooltip) = self.tooltip.as_mut() {
+            response.clone().on_hover_ui_at_pointer(|ui| tooltip(ui));
+        }
+    }
+
+    pub(crate) fn has_context_menu(&self) -> bool {
+        self.context_menu.is_some()
+    }
+
     pub(crate) fn show_context_menu(&mut self, response: &Response) -> bool {
         if let Some(context_menu) = self.context_menu.as_mut() {
             let mut was_open = false;
@@ -310,27 +607,121 @@ pub(crate) fn paint_default_icon(
     openness: f32,
     rect: &Rect,
     interaction: &Interaction,
+    style: &TreeViewStyle,
+    right_to_left: bool,
 ) {
-    let visuals = if interaction.hovered {
-        ui.visuals().widgets.hovered
+    let (visuals, color) = if interaction.hovered {
+        let visuals = ui.visuals().widgets.hovered;
+        (
+            visuals,
+            style
+                .closer_hovered_color
+                .unwrap_or(visuals.fg_stroke.color),
+        )
     } else {
-        ui.visuals().widgets.inactive
+        let visuals = ui.visuals().widgets.inactive;
+        (
+            visuals,
+            style.closer_color.unwrap_or(visuals.fg_stroke.color),
+        )
     };
 
     // Draw a pointy triangle arrow:
-    let rect = Rect::from_center_size(rect.center(), vec2(rect.width(), rect.height()) * 0.75);
+    let rect = Rect::from_center_size(
+        rect.center(),
+        vec2(rect.width(), rect.height()) * style.closer_size,
+    );
     let rect = rect.expand(visuals.expansion);
     let mut points = vec![rect.left_top(), rect.right_top(), rect.center_bottom()];
     use std::f32::consts::TAU;
-    let rotation = emath::Rot2::from_angle(remap(openness, 0.0..=1.0, -TAU / 4.0..=0.0));
+    // A closed arrow points towards the label.
+    let closed_angle = if right_to_left { TAU / 4.0 } else { -TAU / 4.0 };
+    let rotation = emath::Rot2::from_angle(remap(openness, 0.0..=1.0, closed_angle..=0.0));
     for p in &mut points {
         *p = rect.center() + rotation * (*p - rect.center());
     }
 
-    ui.painter().add(Shape::convex_polygon(
-        points,
-        visuals.fg_stroke.color,
-        Stroke::NONE,
+    ui.painter()
+        .add(Shape::convex_polygon(points, color, Stroke::NONE));
+}
+
+/// Get the rectangles for an icon at the start of the remaining space of a row.
+fn icon_rectangles(ui: &Ui, right_to_left: bool) -> (Rect, Rect) {
+    let rect = ui.available_rect_before_wrap();
+    let rect = if right_to_left {
+        Rect::from_min_max(
+            pos2(rect.right() - ui.spacing().icon_width, rect.top()),
+            rect.max,
+        )
+    } else {
+        rect
+    };
+    ui.spacing().icon_rectangles(rect)
+}
+
+/// Paint the default icon of a directory.
+pub(crate) fn paint_folder_icon(ui: &mut Ui, rect: &Rect, is_open: bool, style: &TreeViewStyle) {
+    let rect = rect.shrink(1.0);
+    let p = |x: f32, y: f32| rect.lerp_inside(vec2(x, y));
+    let stroke = style
+        .default_icon_stroke
+        .unwrap_or(ui.visuals().widgets.inactive.fg_stroke);
+
+    if is_open {
+        // The back of the folder with the tab.
+        ui.painter().add(Shape::line(
+            vec![
+                p(0.0, 0.9),
+                p(0.0, 0.15),
+                p(0.35, 0.15),
+                p(0.45, 0.3),
+                p(0.85, 0.3),
+                p(0.85, 0.45),
+            ],
+            stroke,
+        ));
+        // The front of the folder folded open.
+        ui.painter().add(Shape::closed_line(
+            vec![p(0.15, 0.45), p(1.0, 0.45), p(0.85, 0.9), p(0.0, 0.9)],
+            stroke,
+        ));
+    } else {
+        ui.painter().add(Shape::closed_line(
+            vec![
+                p(0.0, 0.15),
+                p(0.35, 0.15),
+                p(0.45, 0.3),
+                p(1.0, 0.3),
+                p(1.0, 0.9),
+                p(0.0, 0.9),
+            ],
+            stroke,
+        ));
+    }
+}
+
+/// Paint the default icon of a leaf.
+pub(crate) fn paint_file_icon(ui: &mut Ui, rect: &Rect, style: &TreeViewStyle) {
+    let rect = rect.shrink(1.0);
+    let p = |x: f32, y: f32| rect.lerp_inside(vec2(x, y));
+    let stroke = style
+        .default_icon_stroke
+        .unwrap_or(ui.visuals().widgets.inactive.fg_stroke);
+
+    // A page with a folded top right corner.
+    ui.painter().add(Shape::closed_line(
+        vec![
+            p(0.15, 0.0),
+            p(0.6, 0.0),
+            p(0.85, 0.25),
+            p(0.85, 1.0),
+            p(0.15, 1.0),
+        ],
+        stroke,
+    ));
+    ui.painter().add(Shape::line(
+        vec![p(0.6, 0.0), p(0.6, 0.25), p(0.85, 0.25)],
+        stroke,
     ));
 }
 
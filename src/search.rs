@@ -0,0 +1,401 @@
+use std::ops::Range;
+
+use egui::{
+    text::{LayoutJob, LayoutSection, TextFormat},
+    Area, Frame, Id, Key, Modifiers, Order, Rect, TextEdit, TextStyle, Ui,
+};
+
+use crate::{NodeState, SearchMode, TreeViewId, TreeViewState};
+
+/// Find the parts of the text that match the query.
+///
+/// Returns the byte ranges of the matched parts or `None` if the
+/// text does not match the query. Matching ignores the case of the text.
+pub(crate) fn match_ranges(text: &str, query: &str, mode: SearchMode) -> Option<Vec<Range<usize>>> {
+    let query = query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .collect::<Vec<_>>();
+    if query.is_empty() {
+        return None;
+    }
+    // Lowercasing can turn a single char into multiple chars. We keep the byte
+    // range of the original char for each lowercase char to map matches back.
+    let chars = text
+        .char_indices()
+        .flat_map(|(index, c)| {
+            let range = index..index + c.len_utf8();
+            c.to_lowercase().map(move |c| (c, range.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    let ranges = match mode {
+        SearchMode::Exact => {
+            let mut ranges = Vec::new();
+            let mut start = 0;
+            while start + query.len() <= chars.len() {
+                let is_match = chars[start..start + query.len()]
+                    .iter()
+                    .zip(query.iter())
+                    .all(|((c, _), q)| c == q);
+                if is_match {
+                    ranges.push(chars[start].1.start..chars[start + query.len() - 1].1.end);
+                    start += query.len();
+                } else {
+                    start += 1;
+                }
+            }
+            ranges
+        }
+        SearchMode::Fuzzy => {
+            let mut ranges = Vec::new();
+            let mut query_chars = query.iter().peekable();
+            for (c, range) in chars.iter() {
+                if query_chars.peek().is_some_and(|q| *q == c) {
+                    query_chars.next();
+                    ranges.push(range.clone());
+                }
+            }
+            if query_chars.peek().is_some() {
+                return None;
+            }
+            ranges
+        }
+    };
+    if ranges.is_empty() {
+        return None;
+    }
+    Some(merge_ranges(ranges))
+}
+
+/// Merge ranges that touch or overlap. The ranges must be sorted.
+fn merge_ranges(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Split the sections of the layout job so that the highlighted ranges
+/// are drawn with the highlight format.
+pub(crate) fn highlight_layout_job(
+    mut job: LayoutJob,
+    highlights: &[Range<usize>],
+    highlight: impl Fn(&TextFormat) -> TextFormat,
+) -> LayoutJob {
+    let mut sections = Vec::new();
+    for section in job.sections.drain(..) {
+        let mut start = section.byte_range.start;
+        for highlight_range in highlights {
+            let highlight_start = highlight_range.start.max(start);
+            let highlight_end = highlight_range.end.min(section.byte_range.end);
+            if highlight_start >= highlight_end {
+                continue;
+            }
+            if start < highlight_start {
+                sections.push(LayoutSection {
+                    leading_space: if start == section.byte_range.start {
+                        section.leading_space
+                    } else {
+                        0.0
+                    },
+                    byte_range: start..highlight_start,
+                    format: section.format.clone(),
+                });
+            }
+            sections.push(LayoutSection {
+                leading_space: if highlight_start == section.byte_range.start {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: highlight_start..highlight_end,
+                format: highlight(&section.format),
+            });
+            start = highlight_end;
+        }
+        if start < section.byte_range.end {
+            sections.push(LayoutSection {
+                leading_space: if start == section.byte_range.start {
+                    section.leading_space
+                } else {
+                    0.0
+                },
+                byte_range: start..section.byte_range.end,
+                format: section.format,
+            });
+        }
+    }
+    job.sections = sections;
+    job
+}
+
+/// Show the search popup of the tree view if it is open.
+pub(crate) fn show_search_popup<NodeIdType: TreeViewId>(
+    ui: &mut Ui,
+    state: &mut TreeViewState<NodeIdType>,
+    tree_id: Id,
+    tree_rect: Rect,
+) {
+    /// The maximum number of search results shown in the popup.
+    const MAX_RESULTS: usize = 10;
+
+    let Some(popup) = state.search_popup.as_mut() else {
+        return;
+    };
+
+    let edit_id = tree_id.with("search popup text edit");
+    let (up, down, enter, escape) = if ui.memory(|m| m.has_focus(edit_id)) {
+        ui.input_mut(|i| {
+            (
+                i.consume_key(Modifiers::NONE, Key::ArrowUp),
+                i.consume_key(Modifiers::NONE, Key::ArrowDown),
+                i.consume_key(Modifiers::NONE, Key::Enter),
+                i.consume_key(Modifiers::NONE, Key::Escape),
+            )
+        })
+    } else {
+        ui.memory_mut(|m| m.request_focus(edit_id));
+        (false, false, false, false)
+    };
+
+    let mut picked = None;
+    let area_response = Area::new(tree_id.with("search popup"))
+        .order(Order::Foreground)
+        .fixed_pos(tree_rect.left_top())
+        .show(ui.ctx(), |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.set_width(tree_rect.width() - ui.spacing().menu_margin.sum().x);
+                let response = ui.add(
+                    TextEdit::singleline(&mut popup.query)
+                        .id(edit_id)
+                        .hint_text("Search")
+                        .desired_width(f32::INFINITY),
+                );
+                if response.changed() {
+                    popup.selected_index = 0;
+                }
+
+                let mut results = state
+                    .node_states
+                    .iter()
+                    .filter_map(|node_state| {
+                        if !node_state.enabled {
+                            return None;
+                        }
+                        let text = node_state.text.as_ref()?;
+                        let highlights = match_ranges(text, &popup.query, SearchMode::Fuzzy)?;
+                        Some((node_state.id, text, highlights))
+                    })
+                    .collect::<Vec<_>>();
+                // Matches with fewer gaps between the matched characters come first.
+                results.sort_by_key(|(_, _, highlights)| highlights.len());
+                results.truncate(MAX_RESULTS);
+
+                if up {
+                    popup.selected_index = popup.selected_index.saturating_sub(1);
+                }
+                if down {
+                    popup.selected_index += 1;
+                }
+                popup.selected_index = popup.selected_index.min(results.len().saturating_sub(1));
+                if enter {
+                    picked = results.get(popup.selected_index).map(|(id, _, _)| *id);
+                }
+
+                for (index, (id, text, highlights)) in results.iter().enumerate() {
+                    let layout_job =
+                        result_layout_job(ui, &state.node_states, *id, text, highlights);
+                    let response = ui.selectable_label(index == popup.selected_index, layout_job);
+                    if response.clicked() {
+                        picked = Some(*id);
+                    }
+                }
+            });
+        })
+        .response;
+
+    // Close the popup when the user clicks outside of it.
+    let clicked_outside = ui.input(|i| {
+        i.pointer.any_pressed()
+            && i.pointer
+                .interact_pos()
+                .is_some_and(|pos| !area_response.rect.contains(pos))
+    });
+
+    if let Some(id) = picked {
+        state.expand_parents_of(id, false);
+        state.selected = Some(id);
+        state.scroll_to_selected = true;
+    }
+    if picked.is_some() || escape || clicked_outside {
+        state.search_popup = None;
+        if !clicked_outside {
+            ui.memory_mut(|m| m.request_focus(tree_id));
+        }
+    }
+}
+
+/// Create the layout job for a search result showing the full path of the node.
+fn result_layout_job<NodeIdType: TreeViewId>(
+    ui: &Ui,
+    node_states: &[NodeState<NodeIdType>],
+    id: NodeIdType,
+    text: &str,
+    highlights: &[Range<usize>],
+) -> LayoutJob {
+    let font_id = TextStyle::Button.resolve(ui.style());
+    let path_format = TextFormat::simple(font_id.clone(), ui.visuals().weak_text_color());
+
+    let mut ancestors = Vec::new();
+    let mut current_parent = node_states
+        .iter()
+        .find(|node_state| node_state.id == id)
+        .and_then(|node_state| node_state.parent_id);
+    while let Some(node_state) =
+        current_parent.and_then(|parent_id| node_states.iter().find(|ns| ns.id == parent_id))
+    {
+        // Flattened directories usually have no text and are skipped.
+        if let Some(text) = node_state.text.as_ref() {
+            ancestors.push(text.as_str());
+        }
+        current_parent = node_state.parent_id;
+    }
+
+    let mut layout_job = LayoutJob::default();
+    for ancestor in ancestors.iter().rev() {
+        layout_job.append(ancestor, 0.0, path_format.clone());
+        layout_job.append(" / ", 0.0, path_format.clone());
+    }
+    let text_start = layout_job.text.len();
+    layout_job.append(
+        text,
+        0.0,
+        TextFormat::simple(font_id, ui.visuals().text_color()),
+    );
+
+    let highlights = highlights
+        .iter()
+        .map(|range| range.start + text_start..range.end + text_start)
+        .collect::<Vec<_>>();
+    let highlight_color = ui.visuals().strong_text_color();
+    highlight_layout_job(layout_job, &highlights, |format| TextFormat {
+        color: highlight_color,
+        ..format.clone()
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use egui::{text::LayoutJob, Color32, TextFormat};
+
+    use super::{highlight_layout_job, match_ranges};
+    use crate::SearchMode;
+
+    #[test]
+    fn exact_matches_ignore_case() {
+        assert_eq!(
+            match_ranges("Hello hello", "HELLO", SearchMode::Exact),
+            Some(vec![0..5, 6..11])
+        );
+        assert_eq!(match_ranges("Hello", "world", SearchMode::Exact), None);
+        assert_eq!(match_ranges("Hello", "", SearchMode::Exact), None);
+    }
+
+    #[test]
+    fn exact_matches_that_touch_are_merged() {
+        assert_eq!(
+            match_ranges("aaaa", "aa", SearchMode::Exact),
+            Some(vec![0..4])
+        );
+    }
+
+    #[test]
+    fn fuzzy_matches_in_order() {
+        assert_eq!(
+            match_ranges("src/builder.rs", "SBR", SearchMode::Fuzzy),
+            Some(vec![0..1, 4..5, 10..11])
+        );
+        assert_eq!(
+            match_ranges("abcd", "abd", SearchMode::Fuzzy),
+            Some(vec![0..2, 3..4])
+        );
+        assert_eq!(match_ranges("abc", "acd", SearchMode::Fuzzy), None);
+        assert_eq!(match_ranges("abc", "ca", SearchMode::Fuzzy), None);
+    }
+
+    #[test]
+    fn lowercase_that_expands_maps_to_the_original_char() {
+        // 'İ' is two bytes long and lowercases to 'i' followed by a combining dot.
+        assert_eq!(
+            match_ranges("İstanbul", "i", SearchMode::Exact),
+            Some(vec![0..2])
+        );
+        assert_eq!(
+            match_ranges("İstanbul", "İS", SearchMode::Exact),
+            Some(vec![0..3])
+        );
+        assert_eq!(
+            match_ranges("İstanbul", "ist", SearchMode::Fuzzy),
+            Some(vec![0..4])
+        );
+    }
+
+    #[test]
+    fn highlights_split_multiple_sections() {
+        let format = |color| TextFormat {
+            color,
+            ..Default::default()
+        };
+        let mut job = LayoutJob::default();
+        job.append("foo ", 2.0, format(Color32::WHITE));
+        job.append("bar", 1.0, format(Color32::GRAY));
+
+        let job = highlight_layout_job(job, &[2..5], |format| TextFormat {
+            background: Color32::RED,
+            ..format.clone()
+        });
+
+        let sections = job
+            .sections
+            .iter()
+            .map(|section| {
+                (
+                    section.byte_range.clone(),
+                    section.leading_space,
+                    section.format.color,
+                    section.format.background,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            vec![
+                (0..2, 2.0, Color32::WHITE, Color32::TRANSPARENT),
+                (2..4, 0.0, Color32::WHITE, Color32::RED),
+                (4..5, 1.0, Color32::GRAY, Color32::RED),
+                (5..7, 0.0, Color32::GRAY, Color32::TRANSPARENT),
+            ]
+        );
+    }
+
+    #[test]
+    fn highlights_keep_the_leading_space_of_a_section() {
+        let mut job = LayoutJob::default();
+        job.append("foo", 4.0, TextFormat::default());
+
+        let job = highlight_layout_job(job, &[0..1], |format| format.clone());
+
+        let sections = job
+            .sections
+            .iter()
+            .map(|section| (section.byte_range.clone(), section.leading_space))
+            .collect::<Vec<_>>();
+        assert_eq!(sections, vec![(0..1, 4.0), (1..3, 0.0)]);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use egui::{pos2, vec2, Align, Align2, Rect, Sense, TextStyle, Ui};
+
+use crate::{node::paint_default_icon, TreeViewData, TreeViewId, TreeViewSettings};
+
+/// The position of a row that was shown in the tree.
+pub(crate) struct StickyRow<NodeIdType> {
+    pub id: NodeIdType,
+    /// The rectangle of the row.
+    pub row: Rect,
+    /// The rectangle of the closer if the node is a directory.
+    pub closer: Option<Rect>,
+    /// The rectangle of the label.
+    pub label: Rect,
+}
+
+/// Show the ancestors of the top-most visible row pinned to the top of the viewport.
+///
+/// The headers show the text of the ancestor nodes. Clicking a header
+/// selects the ancestor and scrolls to it.
+pub(crate) fn show_sticky_headers<NodeIdType: TreeViewId>(
+    ui: &mut Ui,
+    data: &mut TreeViewData<NodeIdType>,
+    settings: &TreeViewSettings,
+) {
+    let viewport_top = ui.clip_rect().top();
+    let rows = data
+        .sticky_rows
+        .iter()
+        .map(|row| (row.id, row))
+        .collect::<HashMap<_, _>>();
+    let parents = data
+        .new_node_states
+        .iter()
+        .map(|node_state| (node_state.id, node_state))
+        .collect::<HashMap<_, _>>();
+
+    // Every header covers a row so the top-most visible row is the first
+    // row below the headers. This can add more headers so we repeat
+    // until the number of headers does not change anymore.
+    let mut headers: Vec<&StickyRow<NodeIdType>> = Vec::new();
+    loop {
+        let headers_bottom = viewport_top + headers.iter().map(|h| h.row.height()).sum::<f32>();
+        let Some(top_row) = data
+            .sticky_rows
+            .iter()
+            .find(|row| row.row.bottom() > headers_bottom)
+        else {
+            break;
+        };
+
+        let mut ancestors = Vec::new();
+        let mut current_parent = parents.get(&top_row.id).and_then(|n| n.parent_id);
+        while let Some(parent_id) = current_parent {
+            // Flattened directories have no row and are skipped.
+            if let Some(row) = rows.get(&parent_id) {
+                ancestors.push(*row);
+            }
+            current_parent = parents.get(&parent_id).and_then(|n| n.parent_id);
+        }
+        ancestors.reverse();
+
+        let mut new_headers = Vec::new();
+        let mut slot_top = viewport_top;
+        for ancestor in ancestors {
+            if ancestor.row.top() >= slot_top {
+                break;
+            }
+            slot_top += ancestor.row.height();
+            new_headers.push(ancestor);
+        }
+        let done = new_headers.len() <= headers.len();
+        headers = new_headers;
+        if done {
+            break;
+        }
+    }
+    if headers.is_empty() {
+        return;
+    }
+
+    let style = data.style;
+    let font_id = TextStyle::Body.resolve(ui.style());
+    let mut slot_top = viewport_top;
+    let mut clicked = None;
+    let mut last_header = Rect::NOTHING;
+    for header in headers.iter() {
+        // Push the header up when the last child of the directory scrolls by.
+        let subtree_bottom = data
+            .sticky_bottoms
+            .get(&header.id)
+            .copied()
+            .unwrap_or(header.row.bottom());
+        let top = slot_top.min(subtree_bottom - header.row.height());
+        let header_rect =
+            Rect::from_x_y_ranges(header.row.x_range(), top..=top + header.row.height());
+        let offset = vec2(0.0, header_rect.top() - header.row.top());
+        slot_top += header.row.height();
+        last_header = header_rect;
+
+        // Every header takes the clicks so the rows below it are not clicked,
+        // but disabled ancestors cannot be selected from their header.
+        let enabled = parents.get(&header.id).is_some_and(|n| n.enabled);
+        let response = ui.interact(
+            header_rect,
+            data.id.with("sticky header").with(header.id),
+            Sense::click(),
+        );
+        ui.painter()
+            .rect_filled(header_rect, 0.0, style.sticky_header_bg_fill);
+        if enabled && response.hovered() {
+            ui.painter()
+                .rect_filled(header_rect, style.rounding, style.hover_bg_fill);
+        }
+        if enabled && response.clicked() {
+            clicked = Some((header.id, header.row, slot_top - viewport_top));
+        }
+
+        if let Some(closer) = header.closer {
+            let (small_rect, _) = ui.spacing().icon_rectangles(closer.translate(offset));
+            paint_default_icon(
+                ui,
+                1.0,
+                &small_rect,
+                &Default::default(),
+                &style,
+                settings.right_to_left,
+            );
+        }
+        if let Some(text) = parents.get(&header.id).and_then(|n| n.text.as_ref()) {
+            let (x, align) = if settings.right_to_left {
+                (header.label.right(), Align2::RIGHT_CENTER)
+            } else {
+                (header.label.left(), Align2::LEFT_CENTER)
+            };
+            ui.painter().text(
+                pos2(x, header.label.center().y + offset.y),
+                align,
+                text,
+                font_id.clone(),
+                style.fg_stroke.color,
+            );
+        }
+    }
+    ui.painter().hline(
+        last_header.x_range(),
+        last_header.bottom(),
+        style.sticky_header_stroke,
+    );
+
+    if let Some((id, row, headers_height)) = clicked {
+        data.peristant.selected = Some(id);
+        // Scroll the ancestor to the position of its header so it is not
+        // covered by the headers of its own ancestors.
+        let target = row.translate(vec2(0.0, row.height() - headers_height));
+        ui.scroll_to_rect(target, Some(Align::TOP));
+        ui.memory_mut(|m| m.request_focus(data.id));
+    }
+}
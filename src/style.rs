@@ -0,0 +1,113 @@
+use egui::{Color32, Rounding, Stroke, Style};
+
+/// The visual parameters of the tree view.
+///
+/// Use [`TreeViewStyle::from_egui_style`] to create a style that matches the
+/// current egui style and change the parts you want to restyle.
+/// The style can be set for the whole tree with
+/// [`TreeView::style`](crate::TreeView::style) and overridden for single nodes
+/// with [`NodeBuilder::style`](crate::node::NodeBuilder::style).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TreeViewStyle {
+    /// Rounding of the row backgrounds and markers.
+    pub rounding: Rounding,
+    /// Text color of a node.
+    pub fg_stroke: Stroke,
+    /// Text color of the selected node while the tree view has focus.
+    pub selection_fg_stroke: Stroke,
+    /// Text color of the selected node while the tree view does not have focus.
+    pub selection_fg_stroke_unfocused: Stroke,
+    /// Background of the selected row while the tree view has focus.
+    pub selection_bg_fill: Color32,
+    /// Background of the selected row while the tree view does not have focus.
+    pub selection_bg_fill_unfocused: Color32,
+    /// Background of the hovered row.
+    ///
+    /// Only used if [`TreeView::row_hover_highlight`](crate::TreeView::row_hover_highlight)
+    /// is enabled.
+    pub hover_bg_fill: Color32,
+    /// Background of every other row.
+    ///
+    /// Only used if [`TreeView::striped`](crate::TreeView::striped) is enabled.
+    pub stripe_bg_fill: Color32,
+    /// Outline of the node whose context menu is open.
+    pub secondary_selection_stroke: Stroke,
+    /// Color of the drop marker between two rows.
+    pub drop_marker_fill: Color32,
+    /// Color of the drop marker over a directory that is the drop target.
+    pub drop_target_fill: Color32,
+    /// Height of the drop marker between two rows.
+    pub drop_marker_height: f32,
+    /// Background of the row that is being dragged.
+    pub drag_overlay_fill: Color32,
+    /// Stroke of the vertical lines that show the indentation level.
+    pub vline_stroke: Stroke,
+    /// Stroke of the vertical line of the directory that contains the selected node.
+    ///
+    /// Only used if [`TreeView::highlight_active_vline`](crate::TreeView::highlight_active_vline)
+    /// is enabled.
+    pub vline_active_stroke: Stroke,
+    /// Space between the closer or icon and the label.
+    pub label_gap: f32,
+    /// Color of the default closer.
+    ///
+    /// If `None`, the closer uses the text color of the row, which follows
+    /// the selection colors.
+    pub closer_color: Option<Color32>,
+    /// Color of the default closer while it is hovered.
+    ///
+    /// If `None`, the closer uses the hovered text color of the ui.
+    pub closer_hovered_color: Option<Color32>,
+    /// Size of the default closer relative to the size of an icon.
+    pub closer_size: f32,
+    /// Stroke of the built-in folder and file icons.
+    ///
+    /// If `None`, the icons use the text color of the row, which follows
+    /// the selection colors.
+    /// Only used if [`TreeView::default_icons`](crate::TreeView::default_icons) is enabled.
+    pub default_icon_stroke: Option<Stroke>,
+    /// Radius of the dot of a [`Decoration`](crate::Decoration).
+    pub decoration_dot_radius: f32,
+    /// Background of the rows pinned to the top by the sticky headers.
+    pub sticky_header_bg_fill: Color32,
+    /// Line below the rows pinned to the top by the sticky headers.
+    pub sticky_header_stroke: Stroke,
+    /// Background of the parts of a label that match the search query.
+    pub search_highlight_fill: Color32,
+    /// Underline of the parts of a label that match the search query.
+    pub search_highlight_stroke: Stroke,
+}
+
+impl TreeViewStyle {
+    /// Create the style of the tree view from an egui style.
+    pub fn from_egui_style(style: &Style) -> Self {
+        let visuals = &style.visuals;
+        Self {
+            rounding: visuals.widgets.active.rounding,
+            fg_stroke: visuals.widgets.noninteractive.fg_stroke,
+            selection_fg_stroke: visuals.selection.stroke,
+            selection_fg_stroke_unfocused: visuals.widgets.inactive.fg_stroke,
+            selection_bg_fill: visuals.selection.bg_fill,
+            selection_bg_fill_unfocused: visuals.widgets.inactive.weak_bg_fill.linear_multiply(0.3),
+            hover_bg_fill: visuals.widgets.hovered.weak_bg_fill,
+            stripe_bg_fill: visuals.faint_bg_color,
+            secondary_selection_stroke: visuals.widgets.inactive.fg_stroke,
+            drop_marker_fill: visuals.selection.bg_fill.linear_multiply(0.6),
+            drop_target_fill: visuals.selection.bg_fill.linear_multiply(0.5),
+            drop_marker_height: 3.0,
+            drag_overlay_fill: visuals.selection.bg_fill.linear_multiply(0.4),
+            vline_stroke: visuals.widgets.noninteractive.bg_stroke,
+            vline_active_stroke: visuals.widgets.inactive.fg_stroke,
+            label_gap: 2.0,
+            closer_color: None,
+            closer_hovered_color: None,
+            closer_size: 0.75,
+            default_icon_stroke: None,
+            decoration_dot_radius: 3.0,
+            sticky_header_bg_fill: visuals.panel_fill,
+            sticky_header_stroke: visuals.widgets.noninteractive.bg_stroke,
+            search_highlight_fill: visuals.selection.bg_fill.linear_multiply(0.5),
+            search_highlight_stroke: visuals.widgets.noninteractive.fg_stroke,
+        }
+    }
+}